@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(
@@ -12,34 +12,330 @@ pub struct Cli {
 
     #[arg(short, long)]
     pub verbose: bool,
+
+    #[arg(short, long, conflicts_with = "verbose")]
+    pub quiet: bool,
+}
+
+impl Cli {
+    pub fn verbosity(&self) -> Verbosity {
+        if self.quiet {
+            Verbosity::Quiet
+        } else if self.verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     Lint {
+        #[arg(value_name = "FILE", required = true)]
+        files: Vec<String>,
+        #[command(flatten)]
+        report: ReportArgs,
+        #[command(flatten)]
+        source: SourceArgs,
+        #[arg(long, value_enum, value_name = "LEVEL", default_value_t = FailOn::Error)]
+        fail_on: FailOn,
+        #[command(flatten)]
+        rules: RuleFilterArgs,
+        #[arg(short, long, value_name = "N")]
+        jobs: Option<usize>,
+    },
+    Fix {
+        #[arg(value_name = "FILE", required = true)]
+        files: Vec<String>,
+        #[arg(short, long, alias = "in-place")]
+        write: bool,
+        #[arg(long, conflicts_with = "write")]
+        dry_run: bool,
+        #[arg(short, long, value_name = "N")]
+        jobs: Option<usize>,
+        #[command(flatten)]
+        rewrite: RewriteArgs,
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+    Format {
+        #[arg(value_name = "FILE", required = true)]
+        files: Vec<String>,
+        #[command(flatten)]
+        layout: LayoutArgs,
+        #[command(flatten)]
+        config: ConfigArgs,
+        #[arg(short, long, alias = "in-place")]
+        write: bool,
+        #[arg(long, conflicts_with = "write")]
+        check: bool,
+        #[arg(short, long, value_name = "N")]
+        jobs: Option<usize>,
+    },
+    Analyze {
         #[arg(value_name = "FILE")]
         file: String,
+        #[arg(long, value_enum, default_value_t = AnalyzeFormat::Text)]
+        format: AnalyzeFormat,
+        #[arg(long, value_name = "N", default_value_t = 4)]
+        geometry_threshold: usize,
+        #[arg(long)]
+        com: bool,
     },
-    Fix {
+    Convert {
         #[arg(value_name = "FILE")]
         file: String,
+        #[arg(long, value_enum, value_name = "FORMAT", required_unless_present_any = ["from", "rpy_to", "materials"])]
+        to: Option<ConvertTarget>,
+        #[arg(long, value_enum, value_name = "FORMAT", conflicts_with = "to")]
+        from: Option<ConvertSource>,
+        #[command(flatten)]
+        export: ExportArgs,
+        #[command(flatten)]
+        transform: TransformArgs,
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
     },
-    Format {
+    Diff {
+        #[arg(value_name = "FILE", required = true)]
+        files: Vec<String>,
+        #[arg(long, value_name = "FILE")]
+        base: Option<String>,
+    },
+    Validate {
         #[arg(value_name = "FILE")]
         file: String,
     },
-    Analyze {
+    Tree {
         #[arg(value_name = "FILE")]
         file: String,
     },
-    Convert {
+    Bundle {
         #[arg(value_name = "FILE")]
         file: String,
+        #[arg(short, long, value_name = "DIR")]
+        output: String,
+        #[arg(long, value_name = "DIR")]
+        package_path: Vec<String>,
     },
-    Diff {
-        #[arg(value_name = "FILE1")]
-        file1: String,
-        #[arg(value_name = "FILE2")]
-        file2: String,
+    Extract {
+        #[arg(value_name = "FILE")]
+        file: String,
+        #[arg(long, value_name = "LINK")]
+        root: String,
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+    Merge {
+        #[arg(value_name = "FILE_A")]
+        first: String,
+        #[arg(value_name = "FILE_B")]
+        second: String,
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+        #[arg(long, value_name = "PREFIX")]
+        prefix_b: Option<String>,
+        #[arg(long, value_name = "LINK")]
+        attach_to: Option<String>,
     },
-} 
\ No newline at end of file
+    Remove {
+        #[arg(value_name = "FILE")]
+        file: String,
+        #[command(flatten)]
+        target: ElementArgs,
+        #[arg(short, long, alias = "in-place")]
+        write: bool,
+        #[arg(long)]
+        cascade: bool,
+    },
+    Rename {
+        #[arg(value_name = "FILE")]
+        file: String,
+        #[command(flatten)]
+        target: RenameArgs,
+        #[arg(short, long, alias = "in-place")]
+        write: bool,
+    },
+    Info {
+        #[arg(value_name = "FILE")]
+        file: String,
+        #[command(flatten)]
+        target: ElementArgs,
+    },
+} 
+
+#[derive(Args, Clone, Debug)]
+pub struct LayoutArgs {
+    #[arg(long)]
+    pub minify: bool,
+    #[arg(long)]
+    pub no_compact: bool,
+    #[arg(long)]
+    pub strip_comments: bool,
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    pub attribute_order: Option<Vec<String>>,
+    #[arg(long, value_name = "NAMES", value_delimiter = ',', value_parser = ["material", "link", "joint", "gazebo", "transmission"])]
+    pub element_order: Option<Vec<String>>,
+    #[arg(long, value_name = "DIGITS")]
+    pub float_precision: Option<usize>,
+    #[arg(long)]
+    pub no_xml_declaration: bool,
+}
+
+#[derive(Args, Clone, Debug)]
+#[group(required = true, multiple = false)]
+pub struct ElementArgs {
+    #[arg(long, value_name = "NAME")]
+    pub link: Option<String>,
+    #[arg(long, value_name = "NAME")]
+    pub joint: Option<String>,
+    #[arg(long, value_name = "NAME")]
+    pub material: Option<String>,
+}
+
+#[derive(Args, Clone, Debug)]
+#[group(required = true, multiple = false)]
+pub struct RenameArgs {
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+    pub link: Option<Vec<String>>,
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+    pub joint: Option<Vec<String>>,
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+    pub material: Option<Vec<String>>,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct RewriteArgs {
+    #[arg(long, value_name = "PATTERN")]
+    pub relabel: Option<String>,
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub sort: Option<SortOrder>,
+    #[arg(long)]
+    pub remove_orphans: bool,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct ReportArgs {
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+    #[arg(long, value_name = "PATH")]
+    pub summary_json: Option<String>,
+    #[arg(long, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+    #[arg(long, conflicts_with = "color")]
+    pub no_color: bool,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct SourceArgs {
+    #[arg(long, value_name = "DIR")]
+    pub package_path: Vec<String>,
+    #[arg(long)]
+    pub lenient: bool,
+    #[arg(long)]
+    pub xacro: bool,
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<String>,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct RuleFilterArgs {
+    #[arg(long, value_name = "RULES", value_delimiter = ',')]
+    pub only: Vec<String>,
+    #[arg(long, value_name = "RULES", value_delimiter = ',')]
+    pub ignore: Vec<String>,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct ExportArgs {
+    #[arg(long, value_name = "LINK")]
+    pub tip: Option<String>,
+    #[arg(long)]
+    pub include_raw_xml: bool,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct TransformArgs {
+    #[arg(long, alias = "angles", value_enum, value_name = "UNIT")]
+    pub rpy_to: Option<AngleUnit>,
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub materials: Option<MaterialLayout>,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct ConfigArgs {
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<String>,
+    #[arg(long)]
+    pub print_config: bool,
+    #[arg(long)]
+    pub xacro: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Sarif,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum FailOn {
+    Error,
+    Warning,
+    Info,
+    Never,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum SortOrder {
+    Alphabetical,
+    Topological,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum AngleUnit {
+    Radians,
+    Degrees,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum MaterialLayout {
+    Extract,
+    Inline,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum AnalyzeFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ConvertTarget {
+    Kdl,
+    Sdf,
+    Mjcf,
+    Dot,
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ConvertSource {
+    Json,
+}
\ No newline at end of file
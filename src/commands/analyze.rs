@@ -1,8 +1,103 @@
-pub fn analyze(file: &str, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+use crate::cli::AnalyzeFormat;
+use crate::utils::{UrdfParser, UrdfProcessor, UrdfDocument, UrdfStats};
+
+pub fn analyze(file: &str, format: AnalyzeFormat, geometry_threshold: usize, com: bool, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
     if verbose {
-        println!("Analyzing: {}", file);
+        eprintln!("Analyzing: {}", file);
+    }
+    
+    let doc = UrdfParser::parse_file(file)?;
+    let stats = UrdfProcessor.analyze(&doc);
+    
+    if format == AnalyzeFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+    
+    println!("Robot: {}", doc.robot.name);
+    print_stats(&stats, verbose);
+    
+    if com {
+        match stats.center_of_mass {
+            Some([x, y, z]) => println!("Center of mass: {} {} {} (in the root link frame)", x, y, z),
+            None => println!("Center of mass: unavailable (needs a single root link and a positive total mass)"),
+        }
+    }
+    
+    for link in &stats.links_without_inertial {
+        eprintln!("warning: link '{}' has no inertial; counted as 0 kg in the total mass", link);
+    }
+    
+    if verbose {
+        print_geometry_counts(&doc, geometry_threshold);
+        print_link_masses(&doc);
     }
     
-    println!("Analyzing {}", file);
     Ok(())
-} 
\ No newline at end of file
+}
+
+fn print_stats(stats: &UrdfStats, verbose: bool) {
+    println!("  Links:      {}", stats.total_links);
+    println!("  Joints:     {}", stats.total_joints);
+    println!("  Materials:  {}", stats.total_materials);
+    println!("  Tree depth: {}", stats.tree_depth);
+    println!("  DOF:        {}", stats.total_dof);
+    println!("  Total mass: {} kg", stats.total_mass);
+    
+    if !stats.joint_types.is_empty() {
+        println!("Joint types:");
+        for (joint_type, count) in &stats.joint_types {
+            let dof = stats.dof_by_type.get(joint_type).copied().unwrap_or(0);
+            println!("  {}: {} ({} DOF)", joint_type, count, dof);
+        }
+    }
+    
+    let props = &stats.link_properties;
+    println!("Link properties:");
+    println!("  With visual:    {}", props.with_visual);
+    println!("  With collision: {}", props.with_collision);
+    println!("  With inertial:  {}", props.with_inertial);
+    println!("  Empty:          {}", props.empty_links);
+    
+    if stats.connected_components.len() > 1 {
+        println!("Connected components: {} (links not joined to each other)", stats.connected_components.len());
+        for (index, links) in stats.connected_components.iter().enumerate() {
+            println!("  #{} ({} links): {}", index + 1, links.len(), links.join(", "));
+        }
+    }
+    
+    println!("Kinematic chains: {}", stats.kinematic_chains.len());
+    for chain in &stats.kinematic_chains {
+        println!("  {} (length {})", chain.name, chain.length);
+        if verbose {
+            println!("    links:  {}", chain.links.join(" -> "));
+            println!("    joints: {}", chain.joints.join(", "));
+        }
+    }
+}
+
+fn print_geometry_counts(doc: &UrdfDocument, threshold: usize) {
+    let counts = UrdfProcessor.link_geometry_counts(doc);
+    let width = counts.iter().map(|count| count.link.len()).max().unwrap_or(0).max("link".len());
+    
+    println!("Geometry per link:");
+    println!("  {:<width$}  {:>7}  {:>10}", "link", "visuals", "collisions", width = width);
+    for count in &counts {
+        let flag = if count.visuals > threshold || count.collisions > threshold {
+            format!("  <- more than {}", threshold)
+        } else {
+            String::new()
+        };
+        println!("  {:<width$}  {:>7}  {:>10}{}", count.link, count.visuals, count.collisions, flag, width = width);
+    }
+}
+
+fn print_link_masses(doc: &UrdfDocument) {
+    let masses = UrdfProcessor.link_masses(doc);
+    let width = masses.iter().map(|mass| mass.link.len()).max().unwrap_or(0).max("link".len());
+    
+    println!("Mass per link:");
+    for mass in &masses {
+        println!("  {:<width$}  {} kg", mass.link, mass.mass, width = width);
+    }
+}
\ No newline at end of file
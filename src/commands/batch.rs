@@ -0,0 +1,100 @@
+use crate::utils::{UrdfParseError, UrdfParser};
+use rayon::ThreadPoolBuilder;
+use rayon::prelude::*;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+// Results come back in input order; -j sets the pool size, otherwise rayon's global pool uses every core.
+pub fn run_parallel<T, F>(files: &[String], jobs: Option<usize>, task: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&str) -> T + Sync,
+{
+    let run = || files.par_iter().map(|file| task(file)).collect();
+    match jobs.map(|jobs| ThreadPoolBuilder::new().num_threads(jobs.max(1)).build()) {
+        Some(Ok(pool)) => pool.install(run),
+        _ => run(),
+    }
+}
+
+// With --xacro, property-only xacro is expanded before parsing; the flag tells callers the text no longer matches the file.
+pub fn read_source(file: &str, xacro: bool) -> Result<(String, bool), UrdfParseError> {
+    let content = fs::read_to_string(file)?;
+    if xacro && let Some(expanded) = UrdfParser::expand_xacro(&content)? {
+        return Ok((expanded, true));
+    }
+    Ok((content, false))
+}
+
+// The exact bytes written for a regenerated document, so `format --check` compares against what `--write` would produce.
+pub fn file_contents(xml: &str) -> String {
+    format!("{}\n", xml.trim_end())
+}
+
+pub fn emit_xml(file: &str, xml: &str, in_place: bool, show_header: bool) -> io::Result<()> {
+    if in_place {
+        write_atomic(file, &file_contents(xml))
+    } else {
+        if show_header {
+            println!("==> {} <==", file);
+        }
+        println!("{}", xml);
+        Ok(())
+    }
+}
+
+// Replaces the file a symlink points at rather than the link, and keeps the original mode bits.
+pub fn write_atomic(path: &str, contents: &str) -> io::Result<()> {
+    let resolved = fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+    let target = resolved.as_path();
+    let permissions = fs::metadata(target).ok().map(|metadata| metadata.permissions());
+    let file_name = target.file_name().and_then(|name| name.to_str()).unwrap_or("urdf");
+    let temp_path = target.with_file_name(format!(".{}.urdfix.tmp", file_name));
+    
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        if let Some(permissions) = permissions {
+            fs::set_permissions(&temp_path, permissions)?;
+        }
+        fs::rename(&temp_path, target)
+    })();
+    
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn write_atomic_keeps_mode_and_symlinks() {
+        use std::os::unix::fs::{PermissionsExt, symlink};
+        
+        let dir = std::env::temp_dir().join(format!("urdfix-write-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let real = dir.join("robot.urdf");
+        let link = dir.join("link.urdf");
+        fs::write(&real, "old").unwrap();
+        fs::set_permissions(&real, fs::Permissions::from_mode(0o640)).unwrap();
+        symlink(&real, &link).unwrap();
+        
+        write_atomic(link.to_str().unwrap(), "new").unwrap();
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&real).unwrap(), "new");
+        assert_eq!(fs::metadata(&real).unwrap().permissions().mode() & 0o777, 0o640);
+        
+        fs::set_permissions(&real, fs::Permissions::from_mode(0o444)).unwrap();
+        write_atomic(real.to_str().unwrap(), "newer").unwrap();
+        assert_eq!(fs::read_to_string(&real).unwrap(), "newer");
+        assert_eq!(fs::metadata(&real).unwrap().permissions().mode() & 0o777, 0o444);
+        
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
\ No newline at end of file
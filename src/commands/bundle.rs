@@ -0,0 +1,105 @@
+use crate::utils::{UrdfParser, UrdfModifier, FormatOptions, GeometryShape, MeshResolver};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MESH_DIR: &str = "meshes";
+
+pub fn bundle(file: &str, output_dir: &str, package_paths: &[String], verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if verbose {
+        eprintln!("Bundling: {}", file);
+    }
+    
+    let mut doc = UrdfParser::parse_file(file)?;
+    let resolver = MeshResolver::new(file, package_paths);
+    
+    let mut copies: HashMap<PathBuf, String> = HashMap::new();
+    let mut taken = HashSet::new();
+    let mut renames: HashMap<String, String> = HashMap::new();
+    let mut unresolved = 0;
+    
+    let filenames: Vec<String> = doc.robot.links.values()
+        .flat_map(|link| {
+            link.visual.iter().map(|visual| &visual.geometry)
+                .chain(link.collision.iter().map(|collision| &collision.geometry))
+        })
+        .flatten()
+        .filter_map(|geometry| match &geometry.shape {
+            GeometryShape::Mesh { filename, .. } => Some(filename.clone()),
+            _ => None,
+        })
+        .collect();
+    
+    for filename in filenames {
+        if renames.contains_key(&filename) {
+            continue;
+        }
+        
+        let source = match resolver.resolve(&filename) {
+            Ok(source) => source,
+            Err(attempted) => {
+                match MeshResolver::find_substitution(&filename) {
+                    Some((package, _)) if !resolver.has_package(package) => {
+                        eprintln!("error: cannot resolve mesh '{}': package '{}' of $(find {}) is not on the package path", filename, package, package);
+                    }
+                    _ => {
+                        let tried: Vec<String> = attempted.iter().map(|path| path.display().to_string()).collect();
+                        eprintln!("error: cannot resolve mesh '{}' (tried: {})", filename, if tried.is_empty() { "no package path".to_string() } else { tried.join(", ") });
+                    }
+                }
+                unresolved += 1;
+                continue;
+            }
+        };
+        
+        let target = copies.entry(source.clone())
+            .or_insert_with(|| unique_name(&source, &mut taken))
+            .clone();
+        renames.insert(filename, format!("{}/{}", MESH_DIR, target));
+    }
+    
+    if unresolved > 0 {
+        return Err(format!("{} mesh file(s) could not be resolved; nothing was written", unresolved).into());
+    }
+    
+    let output_dir = Path::new(output_dir);
+    fs::create_dir_all(output_dir.join(MESH_DIR))?;
+    for (source, target) in &copies {
+        fs::copy(source, output_dir.join(MESH_DIR).join(target))?;
+        if verbose {
+            eprintln!("Copied {} -> {}/{}", source.display(), MESH_DIR, target);
+        }
+    }
+    
+    for link in doc.robot.links.values_mut() {
+        let geometries = link.visual.iter_mut().map(|visual| &mut visual.geometry)
+            .chain(link.collision.iter_mut().map(|collision| &mut collision.geometry));
+        for geometry in geometries.flatten() {
+            if let GeometryShape::Mesh { filename, .. } = &mut geometry.shape
+                && let Some(renamed) = renames.get(filename.as_str())
+            {
+                *filename = renamed.clone();
+            }
+        }
+    }
+    
+    UrdfModifier.format_document(&mut doc, &FormatOptions::default())?;
+    let urdf_name = Path::new(file).file_name().ok_or("input path has no file name")?;
+    fs::write(output_dir.join(urdf_name), format!("{}\n", doc.raw_xml))?;
+    
+    println!("Bundled {} with {} mesh file(s) into {}", file, copies.len(), output_dir.display());
+    Ok(())
+}
+
+fn unique_name(source: &Path, taken: &mut HashSet<String>) -> String {
+    let stem = source.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_else(|| "mesh".to_string());
+    let extension = source.extension().map(|extension| format!(".{}", extension.to_string_lossy())).unwrap_or_default();
+    
+    let mut name = format!("{}{}", stem, extension);
+    let mut index = 1;
+    while !taken.insert(name.clone()) {
+        name = format!("{}_{}{}", stem, index, extension);
+        index += 1;
+    }
+    name
+}
\ No newline at end of file
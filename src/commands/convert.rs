@@ -1,8 +1,111 @@
-pub fn convert(file: &str, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+use crate::cli::{AngleUnit, ConvertSource, ConvertTarget, ExportArgs, MaterialLayout, TransformArgs};
+use crate::output;
+use crate::utils::{UrdfParser, UrdfProcessor, UrdfModifier, UrdfDocument, FormatOptions};
+use std::fs;
+
+pub fn convert(file: &str, to: Option<ConvertTarget>, from: Option<ConvertSource>, export: &ExportArgs, transform: &TransformArgs, output_file: Option<&str>, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
     if verbose {
-        println!("Converting: {}", file);
+        eprintln!("Converting: {}", file);
     }
     
-    println!("Converting {}", file);
+    let mut doc: UrdfDocument = match from {
+        Some(ConvertSource::Json) => serde_json::from_str(&fs::read_to_string(file)?)?,
+        None => UrdfParser::parse_file(file)?,
+    };
+    
+    if let Some(unit) = transform.rpy_to {
+        let convert_angle: fn(f64) -> f64 = match unit {
+            AngleUnit::Radians => f64::to_radians,
+            AngleUnit::Degrees => f64::to_degrees,
+        };
+        let count = UrdfModifier.map_rpy(&mut doc.robot, convert_angle);
+        if verbose {
+            eprintln!("Converted rpy of {} origin(s)", count);
+        }
+    }
+    
+    if let Some(layout) = transform.materials {
+        let changes = match layout {
+            MaterialLayout::Extract => UrdfModifier.extract_materials(&mut doc.robot)?,
+            MaterialLayout::Inline => UrdfModifier.inline_materials(&mut doc.robot)?,
+        };
+        if verbose {
+            for change in &changes {
+                eprintln!("  {}", change);
+            }
+        }
+    }
+    
+    let Some(to) = to else {
+        UrdfModifier.format_document(&mut doc, &FormatOptions::default())?;
+        return write_output(&format!("{}\n", doc.raw_xml), output_file);
+    };
+    
+    let converted = match to {
+        ConvertTarget::Kdl => to_kdl(&doc, export.tip.as_deref())?,
+        ConvertTarget::Sdf => output::to_sdf(&doc)?,
+        ConvertTarget::Mjcf => output::to_mjcf(&doc)?,
+        ConvertTarget::Dot => output::to_dot(&doc),
+        ConvertTarget::Json => to_json(&doc, export.include_raw_xml)?,
+    };
+    
+    write_output(&converted, output_file)
+}
+
+fn write_output(converted: &str, output_file: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    match output_file {
+        Some(path) => fs::write(path, converted)?,
+        None => print!("{}", converted),
+    }
     Ok(())
-} 
\ No newline at end of file
+}
+
+fn to_json(doc: &UrdfDocument, include_raw_xml: bool) -> Result<String, Box<dyn std::error::Error>> {
+    let mut value = serde_json::to_value(doc)?;
+    if !include_raw_xml && let Some(object) = value.as_object_mut() {
+        object.remove("raw_xml");
+    }
+    Ok(format!("{}\n", serde_json::to_string_pretty(&value)?))
+}
+
+fn to_kdl(doc: &UrdfDocument, tip: Option<&str>) -> Result<String, Box<dyn std::error::Error>> {
+    let processor = UrdfProcessor;
+    
+    let roots = processor.find_root_links(doc);
+    let root = match roots.as_slice() {
+        [root] => root.clone(),
+        _ => return Err(format!("KDL export needs exactly one root link, found {}: {:?}", roots.len(), roots).into()),
+    };
+    
+    let tips = match tip {
+        Some(tip) => {
+            if !doc.robot.links.contains_key(tip) {
+                return Err(format!("Tip link '{}' does not exist", tip).into());
+            }
+            vec![tip.to_string()]
+        }
+        None => processor.find_leaf_links(doc),
+    };
+    
+    let mut output = String::new();
+    for tip in &tips {
+        let chain = processor.find_chain(doc, &root, tip)
+            .ok_or_else(|| format!("Tip link '{}' is not reachable from root link '{}'", tip, root))?;
+        
+        output.push_str(&format!("chain {} -> {}\n", root, tip));
+        for (joint_name, link_name) in chain.joints.iter().zip(chain.links.iter().skip(1)) {
+            let joint = &doc.robot.joints[joint_name];
+            let origin = joint.origin.as_ref().map(|o| (o.xyz, o.rpy)).unwrap_or(([0.0; 3], [0.0; 3]));
+            let axis = joint.axis.as_ref().map(|a| a.xyz).unwrap_or([1.0, 0.0, 0.0]);
+            output.push_str(&format!(
+                "  segment link={} joint={} type={} xyz={} {} {} rpy={} {} {} axis={} {} {}\n",
+                link_name, joint_name, joint.joint_type,
+                origin.0[0], origin.0[1], origin.0[2],
+                origin.1[0], origin.1[1], origin.1[2],
+                axis[0], axis[1], axis[2],
+            ));
+        }
+    }
+    
+    Ok(output)
+}
\ No newline at end of file
@@ -1,8 +1,69 @@
-pub fn diff(file1: &str, file2: &str, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+use crate::utils::{UrdfParser, UrdfProcessor, ElementDiff};
+use std::process;
+
+pub fn diff(files: &[String], base: Option<&str>, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let pairs: Vec<(&str, &str)> = match base {
+        Some(base) => files.iter().map(|file| (base, file.as_str())).collect(),
+        None => {
+            if files.len() < 2 {
+                return Err("diff needs at least two files, or --base with one or more files".into());
+            }
+            files.windows(2).map(|pair| (pair[0].as_str(), pair[1].as_str())).collect()
+        }
+    };
+    
+    let mut differs = false;
+    for (index, (file1, file2)) in pairs.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        println!("=== [{}/{}] {} -> {} ===", index + 1, pairs.len(), file1, file2);
+        differs |= diff_pair(file1, file2, verbose)?;
+    }
+    
+    if differs {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn diff_pair(file1: &str, file2: &str, verbose: bool) -> Result<bool, Box<dyn std::error::Error>> {
     if verbose {
         println!("Comparing: {} and {}", file1, file2);
     }
     
-    println!("Comparing {} and {}", file1, file2);
-    Ok(())
-} 
\ No newline at end of file
+    let old = UrdfParser::parse_file(file1)?;
+    let new = UrdfParser::parse_file(file2)?;
+    let diff = UrdfProcessor.diff(&old, &new);
+    
+    if diff.is_empty() {
+        println!("No differences");
+        return Ok(false);
+    }
+    
+    if let Some((old_name, new_name)) = &diff.robot_name {
+        println!("Robot name: {} -> {}", old_name, new_name);
+    }
+    print_element_diff("Links", &diff.links);
+    print_element_diff("Joints", &diff.joints);
+    print_element_diff("Materials", &diff.materials);
+    
+    Ok(true)
+}
+
+fn print_element_diff(title: &str, diff: &ElementDiff) {
+    if diff.is_empty() {
+        return;
+    }
+    
+    println!("{}:", title);
+    for name in &diff.added {
+        println!("  + {}", name);
+    }
+    for name in &diff.removed {
+        println!("  - {}", name);
+    }
+    for (name, fields) in &diff.changed {
+        println!("  ~ {} ({})", name, fields.join("; "));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,20 @@
+use crate::utils::{UrdfParser, UrdfModifier};
+use std::fs;
+
+pub fn extract(file: &str, root: &str, output_file: Option<&str>, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let doc = UrdfParser::parse_file(file)?;
+    let subtree = UrdfModifier.extract_subtree(&doc, root)?;
+    
+    if verbose {
+        eprintln!(
+            "Extracted {} link(s), {} joint(s) and {} material(s) below '{}' from {}",
+            subtree.robot.links.len(), subtree.robot.joints.len(), subtree.robot.materials.len(), root, file
+        );
+    }
+    
+    match output_file {
+        Some(path) => fs::write(path, format!("{}\n", subtree.raw_xml))?,
+        None => println!("{}", subtree.raw_xml),
+    }
+    Ok(())
+}
\ No newline at end of file
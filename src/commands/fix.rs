@@ -1,8 +1,93 @@
-pub fn fix(file: &str, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
-    if verbose {
-        println!("Fixing: {}", file);
+use crate::cli::{ConfigArgs, RewriteArgs, SortOrder, Verbosity};
+use crate::output;
+use crate::utils::{UrdfParser, UrdfModifier, UrdfixConfig, FixOptions, SortMode};
+use super::batch;
+use std::path::Path;
+use std::process;
+
+pub fn fix(files: &[String], write: bool, dry_run: bool, jobs: Option<usize>, rewrite: &RewriteArgs, config_args: &ConfigArgs, verbosity: Verbosity) -> Result<(), Box<dyn std::error::Error>> {
+    let explicit = config_args.config.as_deref().map(Path::new);
+    let options_for = |file: &str| -> Result<FixOptions, String> {
+        let config = UrdfixConfig::for_target(Path::new(file), explicit).map_err(|e| e.to_string())?;
+        let mut options = config.fix_options();
+        if rewrite.relabel.is_some() {
+            options.relabel_pattern = rewrite.relabel.clone();
+        }
+        if rewrite.remove_orphans {
+            options.remove_orphaned_links = true;
+        }
+        if let Some(sort) = rewrite.sort {
+            options.sort_elements = true;
+            options.sort_mode = match sort {
+                SortOrder::Alphabetical => SortMode::Alphabetical,
+                SortOrder::Topological => SortMode::Topological,
+            };
+        }
+        Ok(options)
+    };
+    
+    if config_args.print_config {
+        let config = UrdfixConfig::for_target(Path::new(&files[0]), explicit)?;
+        let effective = UrdfixConfig { lint: config.lint.clone(), ..UrdfixConfig::from_options(&options_for(&files[0])?, &config.format_options()) };
+        print!("{}", effective.to_toml()?);
+        return Ok(());
+    }
+    
+    let results = batch::run_parallel(files, jobs, |file| -> Result<(String, String, Vec<String>, Vec<String>), String> {
+        let options = options_for(file)?;
+        let (content, expanded) = batch::read_source(file, config_args.xacro).map_err(|e| e.to_string())?;
+        if expanded && write {
+            return Err("expanded from xacro; refusing to overwrite the source with --write (drop -w to print the URDF)".to_string());
+        }
+        let mut doc = UrdfParser::parse_string(&content).map_err(|e| e.to_string())?;
+        let original = doc.raw_xml.clone();
+        let changes = UrdfModifier.fix_document(&mut doc, &options).map_err(|e| e.to_string())?;
+        Ok((original, doc.raw_xml, changes, doc.warnings))
+    });
+    
+    let mut failed = 0;
+    let mut would_change = false;
+    for (file, result) in files.iter().zip(results) {
+        match result {
+            Ok((original, xml, changes, warnings)) => {
+                if verbosity != Verbosity::Quiet {
+                    for warning in &warnings {
+                        eprintln!("warning: {}: {}", file, warning);
+                    }
+                }
+                if verbosity == Verbosity::Verbose {
+                    eprintln!("Fixing: {}", file);
+                    for change in &changes {
+                        eprintln!("  {}", change);
+                    }
+                } else if rewrite.relabel.is_some() && verbosity == Verbosity::Normal {
+                    for change in changes.iter().filter(|change| change.starts_with("Relabeled")) {
+                        eprintln!("{}: {}", file, change);
+                    }
+                }
+                
+                if dry_run {
+                    let diff = output::unified_diff(&original, &xml, &format!("a/{}", file), &format!("b/{}", file));
+                    if !diff.is_empty() {
+                        print!("{}", diff);
+                        would_change = true;
+                    }
+                } else {
+                    batch::emit_xml(file, &xml, write, files.len() > 1)?;
+                }
+            }
+            Err(e) => {
+                eprintln!("Error: {}: {}", file, e);
+                failed += 1;
+            }
+        }
     }
     
-    println!("Fixing {}", file);
+    if failed > 0 {
+        return Err(format!("{} of {} files could not be fixed", failed, files.len()).into());
+    }
+    if would_change {
+        process::exit(1);
+    }
     Ok(())
-} 
\ No newline at end of file
+}
\ No newline at end of file
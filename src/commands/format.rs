@@ -1,8 +1,94 @@
-pub fn format(file: &str, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
-    if verbose {
-        println!("Formatting: {}", file);
+use crate::cli::{ConfigArgs, LayoutArgs, Verbosity};
+use crate::utils::{UrdfParser, UrdfModifier, UrdfixConfig, FormatOptions};
+use super::batch;
+use std::path::Path;
+use std::process;
+
+pub fn format(files: &[String], layout: &LayoutArgs, config_args: &ConfigArgs, write: bool, check: bool, jobs: Option<usize>, verbosity: Verbosity) -> Result<(), Box<dyn std::error::Error>> {
+    let explicit = config_args.config.as_deref().map(Path::new);
+    let options_for = |file: &str| -> Result<FormatOptions, String> {
+        let config = UrdfixConfig::for_target(Path::new(file), explicit).map_err(|e| e.to_string())?;
+        Ok(apply_layout(config.format_options(), layout))
+    };
+    
+    if config_args.print_config {
+        let config = UrdfixConfig::for_target(Path::new(&files[0]), explicit)?;
+        let effective = UrdfixConfig { lint: config.lint.clone(), ..UrdfixConfig::from_options(&config.fix_options(), &options_for(&files[0])?) };
+        print!("{}", effective.to_toml()?);
+        return Ok(());
+    }
+    
+    let results = batch::run_parallel(files, jobs, |file| -> Result<(String, String), String> {
+        let options = options_for(file)?;
+        let (content, expanded) = batch::read_source(file, config_args.xacro).map_err(|e| e.to_string())?;
+        if expanded && write {
+            return Err("expanded from xacro; refusing to overwrite the source with --write (drop -w to print the URDF)".to_string());
+        }
+        if expanded && check {
+            return Err("expanded from xacro; --check only applies to plain URDF (drop it to print the URDF)".to_string());
+        }
+        let mut doc = UrdfParser::parse_string(&content).map_err(|e| e.to_string())?;
+        let original = doc.raw_xml.clone();
+        UrdfModifier.format_document(&mut doc, &options).map_err(|e| e.to_string())?;
+        Ok((original, doc.raw_xml))
+    });
+    
+    let mut failed = 0;
+    let mut unformatted = 0;
+    for (file, result) in files.iter().zip(results) {
+        match result {
+            Ok((original, xml)) => {
+                if verbosity == Verbosity::Verbose {
+                    eprintln!("Formatting: {}", file);
+                }
+                if check {
+                    if original != batch::file_contents(&xml) {
+                        if verbosity != Verbosity::Quiet {
+                            println!("Would reformat: {}", file);
+                        }
+                        unformatted += 1;
+                    }
+                } else {
+                    batch::emit_xml(file, &xml, write, files.len() > 1)?;
+                }
+            }
+            Err(e) => {
+                eprintln!("Error: {}: {}", file, e);
+                failed += 1;
+            }
+        }
     }
     
-    println!("Formatting {}", file);
+    if failed > 0 {
+        return Err(format!("{} of {} files could not be formatted", failed, files.len()).into());
+    }
+    if unformatted > 0 {
+        process::exit(1);
+    }
     Ok(())
-} 
\ No newline at end of file
+}
+
+fn apply_layout(mut options: FormatOptions, layout: &LayoutArgs) -> FormatOptions {
+    if layout.minify {
+        options.minify = true;
+    }
+    if layout.no_compact {
+        options.compact_empty_elements = false;
+    }
+    if layout.strip_comments {
+        options.preserve_comments = false;
+    }
+    if let Some(order) = &layout.attribute_order {
+        options.attribute_order = order.clone();
+    }
+    if let Some(order) = &layout.element_order {
+        options.element_order = order.clone();
+    }
+    if layout.no_xml_declaration {
+        options.emit_xml_declaration = false;
+    }
+    if layout.float_precision.is_some() {
+        options.float_precision = layout.float_precision;
+    }
+    options
+}
\ No newline at end of file
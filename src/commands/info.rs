@@ -0,0 +1,189 @@
+use crate::cli::ElementArgs;
+use crate::utils::{UrdfParser, UrdfProcessor, UrdfDocument, Origin, Geometry, GeometryShape, MaterialRef};
+
+pub fn info(file: &str, target: &ElementArgs, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if verbose {
+        eprintln!("Reading: {}", file);
+    }
+    
+    let doc = UrdfParser::parse_file(file)?;
+    match (&target.link, &target.joint, &target.material) {
+        (Some(name), _, _) => link_info(&doc, name),
+        (_, Some(name), _) => joint_info(&doc, name),
+        (_, _, Some(name)) => material_info(&doc, name),
+        _ => Err("one of --link, --joint or --material is required".into()),
+    }
+}
+
+fn link_info(doc: &UrdfDocument, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let link = doc.robot.links.get(name).ok_or_else(|| format!("link '{}' does not exist", name))?;
+    println!("link {}", link.name);
+    
+    match &link.inertial {
+        Some(inertial) => {
+            println!("  inertial:");
+            println!("    mass: {}", inertial.mass);
+            if let Some(origin) = &inertial.origin {
+                println!("    origin: {}", origin_text(origin));
+            }
+            if let Some(inertia) = &inertial.inertia {
+                println!(
+                    "    inertia: ixx={} ixy={} ixz={} iyy={} iyz={} izz={}",
+                    inertia.ixx, inertia.ixy, inertia.ixz, inertia.iyy, inertia.iyz, inertia.izz
+                );
+            }
+        }
+        None => println!("  inertial: none"),
+    }
+    
+    println!("  visuals: {}", link.visual.len());
+    for (index, visual) in link.visual.iter().enumerate() {
+        println!("    [{}]{}", index, visual.name.as_deref().map(|name| format!(" {}", name)).unwrap_or_default());
+        print_placement(visual.origin.as_ref(), visual.geometry.as_ref());
+        if let Some(material) = &visual.material {
+            println!("      material: {}", material_text(material));
+        }
+    }
+    
+    println!("  collisions: {}", link.collision.len());
+    for (index, collision) in link.collision.iter().enumerate() {
+        println!("    [{}]{}", index, collision.name.as_deref().map(|name| format!(" {}", name)).unwrap_or_default());
+        print_placement(collision.origin.as_ref(), collision.geometry.as_ref());
+    }
+    
+    let parents: Vec<_> = doc.robot.joints.values().filter(|joint| joint.child == name).collect();
+    match parents.as_slice() {
+        [] => println!("  parent joint: none (root link)"),
+        _ => {
+            for joint in parents {
+                println!("  parent joint: {} ({}) from {}", joint.name, joint.joint_type, joint.parent);
+            }
+        }
+    }
+    
+    let children: Vec<_> = doc.robot.joints.values().filter(|joint| joint.parent == name).collect();
+    if children.is_empty() {
+        println!("  child joints: none (leaf link)");
+    } else {
+        println!("  child joints:");
+        for joint in children {
+            println!("    {} ({}) → {}", joint.name, joint.joint_type, joint.child);
+        }
+    }
+    
+    let processor = UrdfProcessor;
+    let chain = processor.find_root_links(doc).iter()
+        .find_map(|root| processor.find_chain(doc, root, name));
+    match chain {
+        Some(chain) => {
+            let mut steps = vec![chain.links[0].clone()];
+            for (joint, link) in chain.joints.iter().zip(chain.links.iter().skip(1)) {
+                steps.push(format!("[{}]", joint));
+                steps.push(link.clone());
+            }
+            println!("  chain: {}", steps.join(" → "));
+        }
+        None => println!("  chain: not reachable from a root link"),
+    }
+    
+    Ok(())
+}
+
+fn joint_info(doc: &UrdfDocument, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let joint = doc.robot.joints.get(name).ok_or_else(|| format!("joint '{}' does not exist", name))?;
+    println!("joint {} ({})", joint.name, joint.joint_type);
+    
+    let defined = |link: &str| if doc.robot.links.contains_key(link) { "" } else { " (undefined)" };
+    println!("  parent: {}{}", joint.parent, defined(&joint.parent));
+    println!("  child: {}{}", joint.child, defined(&joint.child));
+    println!("  origin: {}", joint.origin.as_ref().map(origin_text).unwrap_or_else(|| "none (identity)".to_string()));
+    
+    match &joint.axis {
+        Some(axis) if axis.defaulted => println!("  axis: {} (URDF default)", vec3(&axis.xyz)),
+        Some(axis) => println!("  axis: {}", vec3(&axis.xyz)),
+        None => println!("  axis: none"),
+    }
+    match &joint.limit {
+        Some(limit) => println!(
+            "  limit: lower={} upper={} effort={} velocity={}",
+            optional(limit.lower), optional(limit.upper), optional(limit.effort), optional(limit.velocity)
+        ),
+        None => println!("  limit: none"),
+    }
+    match &joint.dynamics {
+        Some(dynamics) => println!("  dynamics: damping={} friction={}", optional(dynamics.damping), optional(dynamics.friction)),
+        None => println!("  dynamics: none"),
+    }
+    match &joint.mimic {
+        Some(mimic) => println!(
+            "  mimic: {} multiplier={} offset={}",
+            mimic.joint, optional(mimic.multiplier), optional(mimic.offset)
+        ),
+        None => println!("  mimic: none"),
+    }
+    
+    let mimicked_by: Vec<&str> = doc.robot.joints.values()
+        .filter(|other| other.mimic.as_ref().is_some_and(|mimic| mimic.joint == name))
+        .map(|other| other.name.as_str())
+        .collect();
+    if !mimicked_by.is_empty() {
+        println!("  mimicked by: {}", mimicked_by.join(", "));
+    }
+    
+    Ok(())
+}
+
+fn material_info(doc: &UrdfDocument, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let material = doc.robot.materials.get(name).ok_or_else(|| format!("material '{}' does not exist", name))?;
+    println!("material {}", material.name);
+    println!("  color: {}", material.color.as_ref().map(|color| format!("{} {} {} {}", color.rgba[0], color.rgba[1], color.rgba[2], color.rgba[3])).unwrap_or_else(|| "none".to_string()));
+    println!("  texture: {}", material.texture.as_ref().map(|texture| texture.filename.as_str()).unwrap_or("none"));
+    
+    let users: Vec<&str> = doc.robot.links.values()
+        .filter(|link| link.visual.iter().any(|visual| visual.material.as_ref().is_some_and(|material| material.name == name)))
+        .map(|link| link.name.as_str())
+        .collect();
+    if users.is_empty() {
+        println!("  used by: none");
+    } else {
+        println!("  used by: {}", users.join(", "));
+    }
+    
+    Ok(())
+}
+
+fn print_placement(origin: Option<&Origin>, geometry: Option<&Geometry>) {
+    if let Some(origin) = origin {
+        println!("      origin: {}", origin_text(origin));
+    }
+    match geometry.map(|geometry| &geometry.shape) {
+        Some(GeometryShape::Box { size }) => println!("      geometry: box size={}", vec3(size)),
+        Some(GeometryShape::Cylinder { radius, length }) => println!("      geometry: cylinder radius={} length={}", radius, length),
+        Some(GeometryShape::Sphere { radius }) => println!("      geometry: sphere radius={}", radius),
+        Some(GeometryShape::Mesh { filename, scale }) => match scale {
+            Some(scale) => println!("      geometry: mesh {} scale={}", filename, vec3(scale)),
+            None => println!("      geometry: mesh {}", filename),
+        },
+        None => println!("      geometry: none"),
+    }
+}
+
+fn material_text(material: &MaterialRef) -> String {
+    match (&material.color, &material.texture) {
+        (None, None) => material.name.clone(),
+        (Some(color), _) => format!("{} (inline, rgba {} {} {} {})", material.name, color.rgba[0], color.rgba[1], color.rgba[2], color.rgba[3]),
+        (None, Some(texture)) => format!("{} (inline, texture {})", material.name, texture.filename),
+    }
+}
+
+fn origin_text(origin: &Origin) -> String {
+    format!("xyz={} rpy={}", vec3(&origin.xyz), vec3(&origin.rpy))
+}
+
+fn vec3(values: &[f64; 3]) -> String {
+    format!("{} {} {}", values[0], values[1], values[2])
+}
+
+fn optional(value: Option<f64>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_else(|| "-".to_string())
+}
\ No newline at end of file
@@ -1,10 +1,230 @@
-use crate::cli::OutputFormat;
+use crate::cli::{ColorChoice, FailOn, OutputFormat, ReportArgs, RuleFilterArgs, SourceArgs, Verbosity};
+use crate::commands::batch;
+use crate::output;
+use crate::utils::{UrdfParser, UrdfProcessor, UrdfIssue, IssueSeverity, IssueCategory, LintOptions, MeshResolver, UrdfixConfig};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::process;
 
-pub fn lint(file: &str, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
-    if verbose {
-        println!("Linting: {}", file);
+#[derive(Serialize)]
+struct LintSummary {
+    errors: usize,
+    warnings: usize,
+    infos: usize,
+    files: usize,
+    failed: bool,
+}
+
+pub fn lint(files: &[String], report: &ReportArgs, source: &SourceArgs, fail_on: FailOn, rules: &RuleFilterArgs, jobs: Option<usize>, verbosity: Verbosity) -> Result<(), Box<dyn std::error::Error>> {
+    let format = report.format;
+    let mut reports: Vec<(String, Vec<UrdfIssue>)> = Vec::new();
+    let mut unreadable = 0;
+    
+    let verbose = verbosity == Verbosity::Verbose;
+    let results = batch::run_parallel(files, jobs, |file| lint_file(file, source).map_err(|e| e.to_string()));
+    for (file, result) in files.iter().zip(results) {
+        if verbose {
+            eprintln!("Linting: {}", file);
+        }
+        match result {
+            Ok((issues, suppressed)) => {
+                if verbose && suppressed > 0 {
+                    eprintln!("  {} issue(s) suppressed by urdfix-disable comments", suppressed);
+                }
+                let issues = issues.into_iter().filter(|issue| selected(issue, rules)).collect();
+                reports.push((file.clone(), issues));
+            }
+            Err(e) => {
+                eprintln!("Error: {}: {}", file, e);
+                unreadable += 1;
+            }
+        }
+    }
+    
+    // Quiet mode only hides findings; --fail-on and the summary still count every issue.
+    let shown: Vec<(String, Vec<UrdfIssue>)> = reports.iter()
+        .map(|(file, issues)| {
+            let issues = issues.iter()
+                .filter(|issue| verbosity != Verbosity::Quiet || issue.severity == IssueSeverity::Error)
+                .cloned()
+                .collect();
+            (file.clone(), issues)
+        })
+        .collect();
+    
+    match format {
+        OutputFormat::Text => {
+            let color = use_color(report);
+            let quiet = verbosity == Verbosity::Quiet;
+            for (index, (file, issues)) in shown.iter().filter(|(_, issues)| !quiet || !issues.is_empty()).enumerate() {
+                if files.len() > 1 {
+                    if index > 0 {
+                        println!();
+                    }
+                    println!("==> {} <==", file);
+                }
+                for issue in issues {
+                    print_issue(issue, color);
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let by_file: Vec<_> = shown.iter()
+                .map(|(file, issues)| json!({ "file": file, "issues": issues }))
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&by_file)?);
+        }
+        OutputFormat::Sarif => {
+            println!("{}", serde_json::to_string_pretty(&output::to_sarif(&shown))?);
+        }
+    }
+    
+    let issues: Vec<UrdfIssue> = reports.iter().flat_map(|(_, issues)| issues.iter().cloned()).collect();
+    let decisive = issues.iter().filter(|issue| fails(&issue.severity, fail_on)).count();
+    let mut summary = summarize(&issues, files.len());
+    summary.failed = decisive > 0 || unreadable > 0;
+    if format == OutputFormat::Text && verbosity != Verbosity::Quiet {
+        print_summary(&summary, &issues);
+        if verbose && reports.len() > 1 {
+            for (file, issues) in &reports {
+                let per_file = summarize(issues, 1);
+                println!("  {}: {} errors, {} warnings, {} info", file, per_file.errors, per_file.warnings, per_file.infos);
+            }
+        }
+    }
+    
+    if let Some(path) = &report.summary_json {
+        write_summary(path, &summary)?;
+    }
+    
+    if unreadable > 0 {
+        return Err(format!("{} of {} files could not be linted", unreadable, files.len()).into());
+    }
+    if decisive > 0 {
+        let level = match fail_on {
+            FailOn::Error => "error",
+            FailOn::Warning => "warning",
+            FailOn::Info => "info",
+            FailOn::Never => "never",
+        };
+        eprintln!("{} issue(s) at or above --fail-on {}", decisive, level);
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn fails(severity: &IssueSeverity, fail_on: FailOn) -> bool {
+    match fail_on {
+        FailOn::Error => *severity == IssueSeverity::Error,
+        FailOn::Warning => *severity != IssueSeverity::Info,
+        FailOn::Info => true,
+        FailOn::Never => false,
     }
+}
+
+// --ignore wins over --only: an issue is reported when it matches --only (or --only is empty) and does not match --ignore.
+fn selected(issue: &UrdfIssue, rules: &RuleFilterArgs) -> bool {
+    let category = issue.category.to_string();
+    let matches = |pattern: &String| *pattern == category || pattern == issue.rule_id;
+    (rules.only.is_empty() || rules.only.iter().any(matches)) && !rules.ignore.iter().any(matches)
+}
+
+// Runs on a worker thread, so nothing is printed here; lint() reports in input order.
+fn lint_file(file: &str, source: &SourceArgs) -> Result<(Vec<UrdfIssue>, usize), Box<dyn std::error::Error>> {
+    let config = UrdfixConfig::for_target(Path::new(file), source.config.as_deref().map(Path::new))?;
+    let options = LintOptions {
+        mesh_resolver: Some(MeshResolver::new(file, &source.package_path)),
+        ..config.lint_options()
+    };
     
-    println!("Linting {}", file);
+    let (content, _) = batch::read_source(file, source.xacro)?;
+    let (doc, parse_errors) = if source.lenient {
+        UrdfParser::parse_string_lenient(&content)?
+    } else {
+        (UrdfParser::parse_string(&content)?, Vec::new())
+    };
+    // The parser's warnings are duplicate definitions, which lint reports as validation/duplicate-element.
+    let mut issues: Vec<UrdfIssue> = parse_errors.into_iter()
+        .map(|error| UrdfIssue {
+            severity: IssueSeverity::Error,
+            category: IssueCategory::Validation,
+            rule_id: "validation/parse-error",
+            message: error.to_string(),
+            element_name: None,
+            suggestion: Some("The element was skipped; fix it and lint again to check it".to_string()),
+        })
+        .collect();
+    issues.extend(UrdfProcessor.lint_with_options(&doc, &options));
+    Ok(UrdfProcessor.apply_suppressions(&doc, issues))
+}
+
+fn use_color(report: &ReportArgs) -> bool {
+    match report.color {
+        _ if report.no_color => false,
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none(),
+    }
+}
+
+fn print_issue(issue: &UrdfIssue, color: bool) {
+    let severity = match (color, &issue.severity) {
+        (false, _) => issue.severity.to_string(),
+        (true, IssueSeverity::Error) => format!("\x1b[1;31m{}\x1b[0m", issue.severity),
+        (true, IssueSeverity::Warning) => format!("\x1b[1;33m{}\x1b[0m", issue.severity),
+        (true, IssueSeverity::Info) => format!("\x1b[1;34m{}\x1b[0m", issue.severity),
+    };
+    println!("{}[{}]: {}", severity, issue.rule_id, issue.message);
+    if let Some(element_name) = &issue.element_name {
+        println!("  --> {}", element_name);
+    }
+    if let Some(suggestion) = &issue.suggestion {
+        println!("  = help: {}", suggestion);
+    }
+}
+
+fn print_summary(summary: &LintSummary, issues: &[UrdfIssue]) {
+    let mut by_category: BTreeMap<String, [usize; 3]> = BTreeMap::new();
+    for issue in issues {
+        let column = match issue.severity {
+            IssueSeverity::Error => 0,
+            IssueSeverity::Warning => 1,
+            IssueSeverity::Info => 2,
+        };
+        by_category.entry(issue.category.to_string()).or_default()[column] += 1;
+    }
+    
+    println!();
+    println!(
+        "Summary: {} errors, {} warnings, {} info across {} categories in {} file(s)",
+        summary.errors, summary.warnings, summary.infos, by_category.len(), summary.files
+    );
+    if by_category.is_empty() {
+        return;
+    }
+    println!("  {:<12} {:>7} {:>8} {:>5}", "category", "error", "warning", "info");
+    for (category, [errors, warnings, infos]) in &by_category {
+        println!("  {:<12} {:>7} {:>8} {:>5}", category, errors, warnings, infos);
+    }
+}
+
+fn summarize(issues: &[UrdfIssue], files: usize) -> LintSummary {
+    let count = |severity: IssueSeverity| issues.iter().filter(|issue| issue.severity == severity).count();
+    LintSummary {
+        errors: count(IssueSeverity::Error),
+        warnings: count(IssueSeverity::Warning),
+        infos: count(IssueSeverity::Info),
+        files,
+        failed: false,
+    }
+}
+
+fn write_summary(path: &str, summary: &LintSummary) -> Result<(), Box<dyn std::error::Error>> {
+    fs::write(path, serde_json::to_string(summary)?)?;
     Ok(())
-} 
\ No newline at end of file
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+use crate::utils::{UrdfParser, UrdfModifier};
+use std::fs;
+
+pub fn merge(first: &str, second: &str, output_file: Option<&str>, prefix: Option<&str>, attach_to: Option<&str>, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut doc = UrdfParser::parse_file(first)?;
+    let mut other = UrdfParser::parse_file(second)?;
+    
+    if let Some(prefix) = prefix {
+        UrdfModifier.prefix_names(&mut other, prefix)?;
+    }
+    
+    let changes = UrdfModifier.merge_documents(&mut doc, other, attach_to)
+        .map_err(|e| match prefix {
+            Some(_) => e.to_string(),
+            None => format!("{}; pass --prefix-b to rename the second robot's elements", e),
+        })?;
+    
+    if verbose {
+        for change in &changes {
+            eprintln!("{}", change);
+        }
+    }
+    
+    match output_file {
+        Some(path) => fs::write(path, format!("{}\n", doc.raw_xml))?,
+        None => println!("{}", doc.raw_xml),
+    }
+    Ok(())
+}
\ No newline at end of file
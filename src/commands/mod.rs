@@ -4,10 +4,28 @@ pub mod format;
 pub mod analyze;
 pub mod convert;
 pub mod diff;
+pub mod validate;
+pub mod tree;
+pub mod bundle;
+pub mod extract;
+pub mod merge;
+pub mod remove;
+pub mod rename;
+pub mod info;
+
+mod batch;
 
 pub use lint::lint;
 pub use fix::fix;
 pub use format::format;
 pub use analyze::analyze;
 pub use convert::convert;
-pub use diff::diff; 
\ No newline at end of file
+pub use diff::diff;
+pub use validate::validate;
+pub use tree::tree;
+pub use bundle::bundle;
+pub use extract::extract;
+pub use merge::merge;
+pub use remove::remove;
+pub use rename::rename;
+pub use info::info; 
\ No newline at end of file
@@ -0,0 +1,45 @@
+use crate::cli::ElementArgs;
+use crate::utils::{UrdfParser, UrdfModifier};
+use super::batch;
+
+pub fn remove(file: &str, target: &ElementArgs, write: bool, cascade: bool, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let (kind, name) = match (&target.link, &target.joint, &target.material) {
+        (Some(name), _, _) => ("link", name),
+        (_, Some(name), _) => ("joint", name),
+        (_, _, Some(name)) => ("material", name),
+        _ => return Err("one of --link, --joint or --material is required".into()),
+    };
+    
+    let mut doc = UrdfParser::parse_file(file)?;
+    let dependents: Vec<String> = match kind {
+        "link" => doc.robot.joints.values()
+            .filter(|joint| &joint.parent == name || &joint.child == name)
+            .map(|joint| joint.name.clone())
+            .collect(),
+        _ => Vec::new(),
+    };
+    
+    if !UrdfModifier.remove_element(&mut doc, kind, name)? {
+        return Err(format!("{} '{}' does not exist in {}", kind, name, file).into());
+    }
+    if verbose {
+        eprintln!("Removed {} '{}'", kind, name);
+    }
+    
+    if cascade {
+        for joint in &dependents {
+            UrdfModifier.remove_element(&mut doc, "joint", joint)?;
+            if verbose {
+                eprintln!("Removed joint '{}'", joint);
+            }
+        }
+    } else if !dependents.is_empty() {
+        eprintln!(
+            "warning: joint(s) {} still reference the removed link '{}'; pass --cascade to remove them",
+            dependents.join(", "), name
+        );
+    }
+    
+    batch::emit_xml(file, &doc.raw_xml, write, false)?;
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use crate::cli::RenameArgs;
+use crate::utils::{UrdfParser, UrdfModifier};
+use super::batch;
+
+pub fn rename(file: &str, target: &RenameArgs, write: bool, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let (kind, names) = match (&target.link, &target.joint, &target.material) {
+        (Some(names), _, _) => ("link", names),
+        (_, Some(names), _) => ("joint", names),
+        (_, _, Some(names)) => ("material", names),
+        _ => return Err("one of --link, --joint or --material is required".into()),
+    };
+    let [old_name, new_name] = names.as_slice() else {
+        return Err(format!("--{} takes the old and the new name", kind).into());
+    };
+    
+    let mut doc = UrdfParser::parse_file(file)?;
+    let exists = |name: &str| match kind {
+        "link" => doc.robot.links.contains_key(name),
+        "joint" => doc.robot.joints.contains_key(name),
+        _ => doc.robot.materials.contains_key(name),
+    };
+    if !exists(old_name) {
+        return Err(format!("{} '{}' does not exist in {}", kind, old_name, file).into());
+    }
+    if exists(new_name) {
+        return Err(format!("{} '{}' already exists in {}; renaming would merge the two", kind, new_name, file).into());
+    }
+    
+    UrdfModifier.rename_element(&mut doc, kind, old_name, new_name)?;
+    if verbose {
+        eprintln!("Renamed {} '{}' -> '{}'", kind, old_name, new_name);
+    }
+    
+    batch::emit_xml(file, &doc.raw_xml, write, false)?;
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+use crate::utils::{UrdfParser, UrdfProcessor, UrdfDocument};
+use std::collections::{HashMap, HashSet};
+
+pub fn tree(file: &str, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if verbose {
+        eprintln!("Reading: {}", file);
+    }
+    
+    let doc = UrdfParser::parse_file(file)?;
+    let processor = UrdfProcessor;
+    let graph = processor.get_dependency_graph(&doc);
+    let roots = processor.find_root_links(&doc);
+    
+    let mut visited = HashSet::new();
+    let mut first = true;
+    
+    for root in &roots {
+        if !first {
+            println!();
+        }
+        first = false;
+        println!("{}", root);
+        print_children(&doc, &graph, root, "", &mut visited, &mut vec![root.clone()]);
+        visited.insert(root.clone());
+    }
+    
+    for link in doc.robot.links.keys() {
+        if visited.contains(link) {
+            continue;
+        }
+        if !first {
+            println!();
+        }
+        first = false;
+        println!("{} (no root: part of a cycle)", link);
+        visited.insert(link.clone());
+        print_children(&doc, &graph, link, "", &mut visited, &mut vec![link.clone()]);
+    }
+    
+    Ok(())
+}
+
+fn print_children(doc: &UrdfDocument, graph: &HashMap<String, Vec<String>>, link: &str, prefix: &str, visited: &mut HashSet<String>, path: &mut Vec<String>) {
+    let Some(children) = graph.get(link) else {
+        return;
+    };
+    
+    let mut seen = HashSet::new();
+    let branches: Vec<(&str, &str, &str)> = children.iter()
+        .filter(|child| seen.insert(child.as_str()))
+        .flat_map(|child| {
+            doc.robot.joints.values()
+                .filter(move |joint| joint.parent == link && &joint.child == child)
+                .map(|joint| (joint.name.as_str(), joint.joint_type.as_str(), joint.child.as_str()))
+        })
+        .collect();
+    
+    for (index, (joint_name, joint_type, child)) in branches.iter().enumerate() {
+        let last = index + 1 == branches.len();
+        let (branch, indent) = if last { ("└─ ", "   ") } else { ("├─ ", "│  ") };
+        
+        if path.iter().any(|name| name == child) {
+            println!("{}{}{} ({}) → {} ↺ back-edge", prefix, branch, joint_name, joint_type, child);
+            continue;
+        }
+        
+        println!("{}{}{} ({}) → {}", prefix, branch, joint_name, joint_type, child);
+        visited.insert(child.to_string());
+        path.push(child.to_string());
+        print_children(doc, graph, child, &format!("{}{}", prefix, indent), visited, path);
+        path.pop();
+    }
+}
\ No newline at end of file
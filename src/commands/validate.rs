@@ -1,36 +1,39 @@
+use crate::utils::{UrdfParser, UrdfProcessor, validate_urdf_structure, validate_xml_structure};
+use std::fs;
+use std::process;
+
 pub fn validate(file: &str, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
     if verbose {
-        println!("Validating URDF file: {}", file);
+        eprintln!("Validating: {}", file);
     }
     
-    // TODO: Implement validation logic
-    // - Parse URDF file
-    // - Validate XML structure
-    // - Check required elements
-    // - Verify joint definitions
-    // - Validate link hierarchy
-    // - Check for circular references
+    let content = fs::read_to_string(file)?;
     
-    println!("Validating {}", file);
-    
-    // Example validation checks:
-    let validation_checks = vec![
-        "✓ XML is well-formed",
-        "✓ Root element is <robot>",
-        "✓ All links have unique names",
-        "✓ All joints have unique names",
-        "✓ Joint parent/child links exist",
-        "✓ No circular dependencies in joint chain"
-    ];
+    if let Err(e) = validate_xml_structure(&content) {
+        println!("error: {}: XML is not well-formed: {}", file, e);
+        process::exit(1);
+    }
     
-    if verbose {
-        println!("Validation results:");
-        for check in validation_checks {
-            println!("  {}", check);
+    let doc = match UrdfParser::parse_string(&content) {
+        Ok(doc) => doc,
+        Err(e) => {
+            println!("error: {}: {}", file, e);
+            process::exit(1);
         }
-    } else {
-        println!("✓ URDF file is valid");
+    };
+    
+    let mut failures = validate_urdf_structure(&doc);
+    if let Err(errors) = UrdfProcessor.validate_kinematic_tree(&doc) {
+        failures.extend(errors);
     }
     
-    Ok(())
-} 
\ No newline at end of file
+    if failures.is_empty() {
+        println!("{}: valid", file);
+        return Ok(());
+    }
+    
+    for failure in &failures {
+        println!("error: {}: {}", file, failure);
+    }
+    process::exit(1);
+}
\ No newline at end of file
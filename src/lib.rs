@@ -0,0 +1,8 @@
+pub mod output;
+pub mod utils;
+
+pub use utils::{
+    UrdfParser, UrdfDocument, UrdfParseError, Robot, Link, Joint, Material,
+    UrdfProcessor, UrdfStats, UrdfIssue, IssueSeverity, IssueCategory, LintOptions,
+    UrdfModifier, FixOptions, FormatOptions, SortMode, MeshResolver,
+};
\ No newline at end of file
@@ -1,10 +1,11 @@
+use clap::Parser;
 use std::process;
 
 mod cli;
 mod commands;
-mod utils;
 
 use cli::{Cli, Commands};
+use urdfix::{output, utils};
 
 fn main() {
     let cli = Cli::parse();
@@ -17,21 +18,43 @@ fn main() {
 
 fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
     match &cli.command {
-        Some(Commands::Lint { file }) => commands::lint(file, cli.verbose),
-        Some(Commands::Fix { file }) => commands::fix(file, cli.verbose),
-        Some(Commands::Format { file }) => commands::format(file, cli.verbose),
-        Some(Commands::Analyze { file }) => commands::analyze(file, cli.verbose),
-        Some(Commands::Convert { file }) => commands::convert(file, cli.verbose),
-        Some(Commands::Diff { file1, file2 }) => commands::diff(file1, file2, cli.verbose),
+        Some(Commands::Lint { files, report, source, fail_on, rules, jobs }) => commands::lint(files, report, source, *fail_on, rules, *jobs, cli.verbosity()),
+        Some(Commands::Fix { files, write, dry_run, jobs, rewrite, config }) => commands::fix(files, *write, *dry_run, *jobs, rewrite, config, cli.verbosity()),
+        Some(Commands::Format { files, layout, config, write, check, jobs }) => commands::format(files, layout, config, *write, *check, *jobs, cli.verbosity()),
+        Some(Commands::Analyze { file, format, geometry_threshold, com }) => commands::analyze(file, *format, *geometry_threshold, *com, cli.verbose),
+        Some(Commands::Convert { file, to, from, export, transform, output }) => commands::convert(file, *to, *from, export, transform, output.as_deref(), cli.verbose),
+        Some(Commands::Diff { files, base }) => commands::diff(files, base.as_deref(), cli.verbose),
+        Some(Commands::Validate { file }) => commands::validate(file, cli.verbose),
+        Some(Commands::Tree { file }) => commands::tree(file, cli.verbose),
+        Some(Commands::Bundle { file, output, package_path }) => commands::bundle(file, output, package_path, cli.verbose),
+        Some(Commands::Extract { file, root, output }) => commands::extract(file, root, output.as_deref(), cli.verbose),
+        Some(Commands::Merge { first, second, output, prefix_b, attach_to }) => commands::merge(first, second, output.as_deref(), prefix_b.as_deref(), attach_to.as_deref(), cli.verbose),
+        Some(Commands::Remove { file, target, write, cascade }) => commands::remove(file, target, *write, *cascade, cli.verbose),
+        Some(Commands::Rename { file, target, write }) => commands::rename(file, target, *write, cli.verbose),
+        Some(Commands::Info { file, target }) => commands::info(file, target, cli.verbose),
         None => {
             println!("No command specified. Use --help for usage information.");
             println!("\nExamples:");
             println!("  urdfix lint robot.urdf");
+            println!("  urdfix -q lint urdf/*.urdf --fail-on warning");
             println!("  urdfix fix robot.urdf");
             println!("  urdfix format robot.urdf");
+            println!("  urdfix validate robot.urdf");
             println!("  urdfix analyze robot.urdf");
-            println!("  urdfix convert robot.urdf");
-            println!("  urdfix diff robot1.urdf robot2.urdf");
+            println!("  urdfix tree robot.urdf");
+            println!("  urdfix info robot.urdf --link forearm");
+            println!("  urdfix bundle robot.urdf -o dist/");
+            println!("  urdfix extract humanoid.urdf --root left_shoulder -o left_arm.urdf");
+            println!("  urdfix merge arm.urdf gripper.urdf --prefix-b gripper_ --attach-to tool0 -o combined.urdf");
+            println!("  urdfix remove robot.urdf --link camera_link --cascade -w");
+            println!("  urdfix rename robot.urdf --link link1 upper_arm -w");
+            println!("  urdfix convert robot.urdf --to kdl --tip tool0");
+            println!("  urdfix convert robot.urdf --to sdf -o robot.sdf");
+            println!("  urdfix convert robot.json --from json -o robot.urdf");
+            println!("  urdfix convert robot.urdf --rpy-to radians -o robot.urdf");
+            println!("  urdfix convert robot.urdf --materials extract -o robot.urdf");
+            println!("  urdfix diff robot1.urdf robot2.urdf robot3.urdf");
+            println!("  urdfix diff --base robot.urdf robot_v2.urdf robot_v3.urdf");
             Ok(())
         }
     }
@@ -0,0 +1,50 @@
+use crate::utils::{UrdfDocument, UrdfProcessor};
+
+pub fn to_dot(doc: &UrdfDocument) -> String {
+    let processor = UrdfProcessor;
+    let graph = processor.get_dependency_graph(doc);
+    let roots = processor.find_root_links(doc);
+    
+    let mut output = String::new();
+    output.push_str(&format!("digraph {} {{\n", quote(&doc.robot.name)));
+    output.push_str("  rankdir=TB;\n");
+    output.push_str("  node [shape=box, style=rounded];\n");
+    
+    for name in doc.robot.links.keys() {
+        let attributes = if roots.contains(name) {
+            " [style=\"rounded,filled\", fillcolor=lightblue]"
+        } else if !graph.contains_key(name) {
+            " [peripheries=2]"
+        } else {
+            ""
+        };
+        output.push_str(&format!("  {}{};\n", quote(name), attributes));
+    }
+    
+    let mut missing: Vec<&str> = doc.robot.joints.values()
+        .flat_map(|joint| [joint.parent.as_str(), joint.child.as_str()])
+        .filter(|name| !doc.robot.links.contains_key(*name))
+        .collect();
+    missing.sort();
+    missing.dedup();
+    for name in missing {
+        output.push_str(&format!("  {} [style=dashed, color=red];\n", quote(name)));
+    }
+    
+    for joint in doc.robot.joints.values() {
+        output.push_str(&format!(
+            "  {} -> {} [label={}];\n",
+            quote(&joint.parent),
+            quote(&joint.child),
+            quote(&format!("{}\n({})", joint.name, joint.joint_type)),
+        ));
+    }
+    
+    output.push_str("}\n");
+    output
+}
+
+fn quote(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
\ No newline at end of file
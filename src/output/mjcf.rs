@@ -0,0 +1,338 @@
+use crate::utils::{UrdfDocument, UrdfParseError, UrdfProcessor, Link, Joint, Geometry, GeometryShape};
+use crate::utils::transform::{Mat3, Pose, Vec3, IDENTITY, compose, origin_pose, quat_to_matrix};
+use quick_xml::Writer;
+use quick_xml::events::{BytesEnd, BytesStart, Event};
+use std::collections::HashSet;
+
+struct MassProperties {
+    mass: f64,
+    com: Vec3,
+    inertia: Mat3,
+}
+
+struct Geom {
+    pose: Pose,
+    shape: GeometryShape,
+    visual: bool,
+}
+
+struct Body {
+    name: String,
+    pose: Pose,
+    joint: Option<Joint>,
+    mass: Option<MassProperties>,
+    geoms: Vec<Geom>,
+    children: Vec<Body>,
+}
+
+pub fn to_mjcf(doc: &UrdfDocument) -> Result<String, UrdfParseError> {
+    let processor = UrdfProcessor;
+    
+    if let Some(cycle) = processor.find_cycle(doc) {
+        return Err(UrdfParseError::InvalidStructure(format!(
+            "MJCF export needs a kinematic tree, but the robot contains a cycle: {}", cycle.join(" -> ")
+        )));
+    }
+    
+    let roots = processor.find_root_links(doc);
+    let root = match roots.as_slice() {
+        [root] => root,
+        _ => {
+            return Err(UrdfParseError::InvalidStructure(format!(
+                "MJCF export needs exactly one root link, found {}: {:?}", roots.len(), roots
+            )));
+        }
+    };
+    
+    let mut children = HashSet::new();
+    for joint in doc.robot.joints.values() {
+        if !children.insert(joint.child.as_str()) {
+            return Err(UrdfParseError::InvalidStructure(format!(
+                "MJCF export needs a kinematic tree, but link '{}' has more than one parent joint", joint.child
+            )));
+        }
+        if !doc.robot.links.contains_key(&joint.child) {
+            return Err(UrdfParseError::InvalidStructure(format!(
+                "Joint '{}' references non-existent child link '{}'", joint.name, joint.child
+            )));
+        }
+    }
+    
+    let root_body = build_body(doc, &doc.robot.links[root.as_str()], IDENTITY, None)?;
+    
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    let mut mujoco = BytesStart::new("mujoco");
+    mujoco.push_attribute(("model", doc.robot.name.as_str()));
+    writer.write_event(Event::Start(mujoco))?;
+    
+    let mut compiler = BytesStart::new("compiler");
+    compiler.push_attribute(("angle", "radian"));
+    writer.write_event(Event::Empty(compiler))?;
+    
+    let mut meshes = Vec::new();
+    collect_meshes(&root_body, &mut meshes);
+    if !meshes.is_empty() {
+        writer.write_event(Event::Start(BytesStart::new("asset")))?;
+        for (index, (filename, scale)) in meshes.iter().enumerate() {
+            let mut mesh = BytesStart::new("mesh");
+            mesh.push_attribute(("name", format!("mesh_{}", index).as_str()));
+            mesh.push_attribute(("file", filename.as_str()));
+            if let Some(scale) = scale {
+                mesh.push_attribute(("scale", format_values(scale).as_str()));
+            }
+            writer.write_event(Event::Empty(mesh))?;
+        }
+        writer.write_event(Event::End(BytesEnd::new("asset")))?;
+    }
+    
+    writer.write_event(Event::Start(BytesStart::new("worldbody")))?;
+    write_body(&mut writer, &root_body, &meshes)?;
+    writer.write_event(Event::End(BytesEnd::new("worldbody")))?;
+    
+    writer.write_event(Event::End(BytesEnd::new("mujoco")))?;
+    
+    let mut output = String::from_utf8(writer.into_inner())
+        .map_err(|e| UrdfParseError::InvalidStructure(format!("UTF-8 error: {}", e)))?;
+    output.push('\n');
+    Ok(output)
+}
+
+fn build_body(doc: &UrdfDocument, link: &Link, pose: Pose, joint: Option<&Joint>) -> Result<Body, UrdfParseError> {
+    if let Some(joint) = joint
+        && !matches!(joint.joint_type.as_str(), "revolute" | "continuous" | "prismatic" | "floating")
+    {
+        return Err(UrdfParseError::InvalidStructure(format!(
+            "joint '{}' has type '{}', which has no MJCF equivalent", joint.name, joint.joint_type
+        )));
+    }
+    
+    let mut body = Body {
+        name: link.name.clone(),
+        pose,
+        joint: joint.cloned(),
+        mass: None,
+        geoms: Vec::new(),
+        children: Vec::new(),
+    };
+    add_link(doc, &mut body, link, IDENTITY)?;
+    Ok(body)
+}
+
+// Fixed joints have no MJCF counterpart, so the child's geoms, mass and subtree are folded into the parent body.
+fn add_link(doc: &UrdfDocument, body: &mut Body, link: &Link, frame: Pose) -> Result<(), UrdfParseError> {
+    if let Some(inertial) = link.inertial.as_ref().filter(|inertial| inertial.mass > 0.0) {
+        let local = compose(frame, origin_pose(inertial.origin.as_ref()));
+        let rotation = quat_to_matrix(local.quat);
+        let inertia = inertial.inertia.as_ref()
+            .map(|i| [[i.ixx, i.ixy, i.ixz], [i.ixy, i.iyy, i.iyz], [i.ixz, i.iyz, i.izz]])
+            .unwrap_or([[0.0; 3]; 3]);
+        let properties = MassProperties {
+            mass: inertial.mass,
+            com: local.pos,
+            inertia: mat_mul(&mat_mul(&rotation, &inertia), &transpose(&rotation)),
+        };
+        body.mass = Some(match body.mass.take() {
+            Some(existing) => combine(existing, properties),
+            None => properties,
+        });
+    }
+    
+    let visuals = link.visual.iter().map(|visual| (visual.origin.as_ref(), visual.geometry.as_ref(), true));
+    let collisions = link.collision.iter().map(|collision| (collision.origin.as_ref(), collision.geometry.as_ref(), false));
+    for (origin, geometry, visual) in visuals.chain(collisions) {
+        if let Some(Geometry { shape }) = geometry {
+            body.geoms.push(Geom {
+                pose: compose(frame, origin_pose(origin)),
+                shape: shape.clone(),
+                visual,
+            });
+        }
+    }
+    
+    for joint in doc.robot.joints.values().filter(|joint| joint.parent == link.name) {
+        let child = &doc.robot.links[joint.child.as_str()];
+        let child_frame = compose(frame, origin_pose(joint.origin.as_ref()));
+        if joint.joint_type == "fixed" {
+            add_link(doc, body, child, child_frame)?;
+        } else {
+            body.children.push(build_body(doc, child, child_frame, Some(joint))?);
+        }
+    }
+    
+    Ok(())
+}
+
+fn combine(a: MassProperties, b: MassProperties) -> MassProperties {
+    let mass = a.mass + b.mass;
+    let com: Vec3 = std::array::from_fn(|i| (a.mass * a.com[i] + b.mass * b.com[i]) / mass);
+    
+    let mut inertia = [[0.0; 3]; 3];
+    for part in [&a, &b] {
+        let d: Vec3 = std::array::from_fn(|i| part.com[i] - com[i]);
+        let d2 = d[0] * d[0] + d[1] * d[1] + d[2] * d[2];
+        for (i, row) in inertia.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                let identity = if i == j { 1.0 } else { 0.0 };
+                *value += part.inertia[i][j] + part.mass * (d2 * identity - d[i] * d[j]);
+            }
+        }
+    }
+    
+    MassProperties { mass, com, inertia }
+}
+
+fn collect_meshes(body: &Body, meshes: &mut Vec<(String, Option<Vec3>)>) {
+    for geom in &body.geoms {
+        if let GeometryShape::Mesh { filename, scale } = &geom.shape
+            && mesh_index(meshes, filename, scale).is_none()
+        {
+            meshes.push((filename.clone(), *scale));
+        }
+    }
+    for child in &body.children {
+        collect_meshes(child, meshes);
+    }
+}
+
+fn mesh_index(meshes: &[(String, Option<Vec3>)], filename: &str, scale: &Option<Vec3>) -> Option<usize> {
+    meshes.iter().position(|(known, known_scale)| known == filename && known_scale == scale)
+}
+
+fn write_body(writer: &mut Writer<Vec<u8>>, body: &Body, meshes: &[(String, Option<Vec3>)]) -> Result<(), UrdfParseError> {
+    let mut element = BytesStart::new("body");
+    element.push_attribute(("name", body.name.as_str()));
+    push_pose(&mut element, body.pose);
+    writer.write_event(Event::Start(element))?;
+    
+    if let Some(joint) = &body.joint {
+        write_joint(writer, joint)?;
+    }
+    
+    if let Some(mass) = &body.mass {
+        let i = &mass.inertia;
+        let mut inertial = BytesStart::new("inertial");
+        inertial.push_attribute(("pos", format_values(&mass.com).as_str()));
+        inertial.push_attribute(("mass", mass.mass.to_string().as_str()));
+        inertial.push_attribute(("fullinertia", format_values(&[i[0][0], i[1][1], i[2][2], i[0][1], i[0][2], i[1][2]]).as_str()));
+        writer.write_event(Event::Empty(inertial))?;
+    }
+    
+    for geom in &body.geoms {
+        let mut element = BytesStart::new("geom");
+        match &geom.shape {
+            GeometryShape::Box { size } => {
+                element.push_attribute(("type", "box"));
+                element.push_attribute(("size", format_values(&size.map(|extent| extent / 2.0)).as_str()));
+            }
+            GeometryShape::Cylinder { radius, length } => {
+                element.push_attribute(("type", "cylinder"));
+                element.push_attribute(("size", format_values(&[*radius, length / 2.0]).as_str()));
+            }
+            GeometryShape::Sphere { radius } => {
+                element.push_attribute(("type", "sphere"));
+                element.push_attribute(("size", radius.to_string().as_str()));
+            }
+            GeometryShape::Mesh { filename, scale } => {
+                let index = mesh_index(meshes, filename, scale).unwrap_or_default();
+                element.push_attribute(("type", "mesh"));
+                element.push_attribute(("mesh", format!("mesh_{}", index).as_str()));
+            }
+        }
+        push_pose(&mut element, geom.pose);
+        if geom.visual {
+            element.push_attribute(("contype", "0"));
+            element.push_attribute(("conaffinity", "0"));
+            element.push_attribute(("group", "1"));
+            element.push_attribute(("density", "0"));
+        }
+        writer.write_event(Event::Empty(element))?;
+    }
+    
+    for child in &body.children {
+        write_body(writer, child, meshes)?;
+    }
+    
+    writer.write_event(Event::End(BytesEnd::new("body")))?;
+    Ok(())
+}
+
+fn write_joint(writer: &mut Writer<Vec<u8>>, joint: &Joint) -> Result<(), UrdfParseError> {
+    if joint.joint_type == "floating" {
+        let mut element = BytesStart::new("freejoint");
+        element.push_attribute(("name", joint.name.as_str()));
+        writer.write_event(Event::Empty(element))?;
+        return Ok(());
+    }
+    
+    let mut element = BytesStart::new("joint");
+    element.push_attribute(("name", joint.name.as_str()));
+    element.push_attribute(("type", if joint.joint_type == "prismatic" { "slide" } else { "hinge" }));
+    let axis = joint.axis.as_ref().map(|axis| axis.xyz).unwrap_or([1.0, 0.0, 0.0]);
+    element.push_attribute(("axis", format_values(&axis).as_str()));
+    
+    let range = joint.limit.as_ref().and_then(|limit| limit.lower.zip(limit.upper));
+    match range {
+        Some((lower, upper)) if joint.joint_type != "continuous" => {
+            element.push_attribute(("limited", "true"));
+            element.push_attribute(("range", format_values(&[lower, upper]).as_str()));
+        }
+        _ => element.push_attribute(("limited", "false")),
+    }
+    
+    if let Some(dynamics) = &joint.dynamics {
+        if let Some(damping) = dynamics.damping {
+            element.push_attribute(("damping", damping.to_string().as_str()));
+        }
+        if let Some(friction) = dynamics.friction {
+            element.push_attribute(("frictionloss", friction.to_string().as_str()));
+        }
+    }
+    
+    writer.write_event(Event::Empty(element))?;
+    Ok(())
+}
+
+fn push_pose(element: &mut BytesStart, pose: Pose) {
+    if pose.pos != [0.0; 3] {
+        element.push_attribute(("pos", format_values(&pose.pos).as_str()));
+    }
+    if pose.quat != IDENTITY.quat {
+        element.push_attribute(("quat", format_values(&pose.quat).as_str()));
+    }
+}
+
+fn mat_mul(a: &Mat3, b: &Mat3) -> Mat3 {
+    std::array::from_fn(|i| std::array::from_fn(|j| (0..3).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+fn transpose(m: &Mat3) -> Mat3 {
+    std::array::from_fn(|i| std::array::from_fn(|j| m[j][i]))
+}
+
+fn format_values(values: &[f64]) -> String {
+    values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::UrdfParser;
+
+    #[test]
+    fn mesh_assets_are_shared_by_filename_and_scale() {
+        let doc = UrdfParser::parse_string(r#"<robot name="r">
+  <link name="a">
+    <visual><geometry><mesh filename="arm.stl"/></geometry></visual>
+    <collision><geometry><mesh filename="arm.stl"/></geometry></collision>
+  </link>
+  <link name="b">
+    <visual><geometry><mesh filename="arm.stl" scale="2 2 2"/></geometry></visual>
+  </link>
+  <joint name="j" type="revolute"><parent link="a"/><child link="b"/><axis xyz="0 0 1"/><limit lower="-1" upper="1" effort="1" velocity="1"/></joint>
+</robot>"#).unwrap();
+        let output = to_mjcf(&doc).unwrap();
+        assert_eq!(output.matches("<mesh ").count(), 2);
+        assert_eq!(output.matches(r#"mesh="mesh_0""#).count(), 2);
+        assert_eq!(output.matches(r#"mesh="mesh_1""#).count(), 1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+pub mod dot;
+pub mod mjcf;
+pub mod sarif;
+pub mod sdf;
+pub mod unified_diff;
+
+pub use dot::to_dot;
+pub use mjcf::to_mjcf;
+pub use sarif::to_sarif;
+pub use sdf::to_sdf;
+pub use unified_diff::unified_diff;
\ No newline at end of file
@@ -0,0 +1,59 @@
+use crate::utils::{UrdfIssue, IssueSeverity};
+use serde_json::{json, Value};
+
+pub fn to_sarif(reports: &[(String, Vec<UrdfIssue>)]) -> Value {
+    let mut rule_ids: Vec<&str> = reports.iter()
+        .flat_map(|(_, issues)| issues.iter().map(|issue| issue.rule_id))
+        .collect();
+    rule_ids.sort();
+    rule_ids.dedup();
+    
+    let rules: Vec<Value> = rule_ids.iter()
+        .map(|id| json!({ "id": id, "name": id }))
+        .collect();
+    
+    let results: Vec<Value> = reports.iter()
+        .flat_map(|(file, issues)| issues.iter().map(move |issue| json!({
+            "ruleId": issue.rule_id,
+            "level": sarif_level(&issue.severity),
+            "message": { "text": issue_text(issue) },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": file },
+                    "region": { "startLine": 1, "startColumn": 1 }
+                }
+            }]
+        })))
+        .collect();
+    
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "urdfix",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/pratyaypandey/urdfix",
+                    "rules": rules
+                }
+            },
+            "results": results
+        }]
+    })
+}
+
+fn sarif_level(severity: &IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Error => "error",
+        IssueSeverity::Warning => "warning",
+        IssueSeverity::Info => "note",
+    }
+}
+
+fn issue_text(issue: &UrdfIssue) -> String {
+    match &issue.suggestion {
+        Some(suggestion) => format!("{} ({})", issue.message, suggestion),
+        None => issue.message.clone(),
+    }
+}
\ No newline at end of file
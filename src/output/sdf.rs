@@ -0,0 +1,209 @@
+use crate::utils::{UrdfDocument, UrdfParseError, Link, Joint, Origin, Geometry, GeometryShape};
+use quick_xml::Writer;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+
+const SDF_VERSION: &str = "1.7";
+const CONTINUOUS_LIMIT: f64 = 1e16;
+
+pub fn to_sdf(doc: &UrdfDocument) -> Result<String, UrdfParseError> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", None, None)))?;
+    
+    let mut sdf = BytesStart::new("sdf");
+    sdf.push_attribute(("version", SDF_VERSION));
+    writer.write_event(Event::Start(sdf))?;
+    
+    let mut model = BytesStart::new("model");
+    model.push_attribute(("name", doc.robot.name.as_str()));
+    writer.write_event(Event::Start(model))?;
+    
+    for link in doc.robot.links.values() {
+        write_link(&mut writer, doc, link)?;
+    }
+    
+    for joint in doc.robot.joints.values() {
+        write_joint(&mut writer, joint)?;
+    }
+    
+    writer.write_event(Event::End(BytesEnd::new("model")))?;
+    writer.write_event(Event::End(BytesEnd::new("sdf")))?;
+    
+    let mut output = String::from_utf8(writer.into_inner())
+        .map_err(|e| UrdfParseError::InvalidStructure(format!("UTF-8 error: {}", e)))?;
+    output.push('\n');
+    Ok(output)
+}
+
+fn write_link(writer: &mut Writer<Vec<u8>>, doc: &UrdfDocument, link: &Link) -> Result<(), UrdfParseError> {
+    let mut element = BytesStart::new("link");
+    element.push_attribute(("name", link.name.as_str()));
+    writer.write_event(Event::Start(element))?;
+    
+    // URDF places a child link at its parent joint's origin; SDF 1.7 expresses that with relative_to.
+    if let Some(joint) = doc.robot.joints.values().find(|joint| joint.child == link.name) {
+        write_pose(writer, None, Some(&joint.name))?;
+    }
+    
+    if let Some(inertial) = &link.inertial {
+        writer.write_event(Event::Start(BytesStart::new("inertial")))?;
+        write_pose(writer, inertial.origin.as_ref(), None)?;
+        write_text(writer, "mass", &inertial.mass.to_string())?;
+        if let Some(inertia) = &inertial.inertia {
+            writer.write_event(Event::Start(BytesStart::new("inertia")))?;
+            write_text(writer, "ixx", &inertia.ixx.to_string())?;
+            write_text(writer, "ixy", &inertia.ixy.to_string())?;
+            write_text(writer, "ixz", &inertia.ixz.to_string())?;
+            write_text(writer, "iyy", &inertia.iyy.to_string())?;
+            write_text(writer, "iyz", &inertia.iyz.to_string())?;
+            write_text(writer, "izz", &inertia.izz.to_string())?;
+            writer.write_event(Event::End(BytesEnd::new("inertia")))?;
+        }
+        writer.write_event(Event::End(BytesEnd::new("inertial")))?;
+    }
+    
+    for (index, visual) in link.visual.iter().enumerate() {
+        let name = visual.name.clone().unwrap_or_else(|| format!("{}_visual_{}", link.name, index));
+        write_shape(writer, "visual", &name, visual.origin.as_ref(), visual.geometry.as_ref())?;
+    }
+    
+    for (index, collision) in link.collision.iter().enumerate() {
+        let name = collision.name.clone().unwrap_or_else(|| format!("{}_collision_{}", link.name, index));
+        write_shape(writer, "collision", &name, collision.origin.as_ref(), collision.geometry.as_ref())?;
+    }
+    
+    writer.write_event(Event::End(BytesEnd::new("link")))?;
+    Ok(())
+}
+
+fn write_shape(writer: &mut Writer<Vec<u8>>, tag: &str, name: &str, origin: Option<&Origin>, geometry: Option<&Geometry>) -> Result<(), UrdfParseError> {
+    let mut element = BytesStart::new(tag);
+    element.push_attribute(("name", name));
+    writer.write_event(Event::Start(element))?;
+    
+    write_pose(writer, origin, None)?;
+    
+    if let Some(geometry) = geometry {
+        writer.write_event(Event::Start(BytesStart::new("geometry")))?;
+        match &geometry.shape {
+            GeometryShape::Box { size } => {
+                writer.write_event(Event::Start(BytesStart::new("box")))?;
+                write_text(writer, "size", &format_values(size))?;
+                writer.write_event(Event::End(BytesEnd::new("box")))?;
+            }
+            GeometryShape::Cylinder { radius, length } => {
+                writer.write_event(Event::Start(BytesStart::new("cylinder")))?;
+                write_text(writer, "radius", &radius.to_string())?;
+                write_text(writer, "length", &length.to_string())?;
+                writer.write_event(Event::End(BytesEnd::new("cylinder")))?;
+            }
+            GeometryShape::Sphere { radius } => {
+                writer.write_event(Event::Start(BytesStart::new("sphere")))?;
+                write_text(writer, "radius", &radius.to_string())?;
+                writer.write_event(Event::End(BytesEnd::new("sphere")))?;
+            }
+            GeometryShape::Mesh { filename, scale } => {
+                writer.write_event(Event::Start(BytesStart::new("mesh")))?;
+                write_text(writer, "uri", filename)?;
+                if let Some(scale) = scale {
+                    write_text(writer, "scale", &format_values(scale))?;
+                }
+                writer.write_event(Event::End(BytesEnd::new("mesh")))?;
+            }
+        }
+        writer.write_event(Event::End(BytesEnd::new("geometry")))?;
+    }
+    
+    writer.write_event(Event::End(BytesEnd::new(tag)))?;
+    Ok(())
+}
+
+fn write_joint(writer: &mut Writer<Vec<u8>>, joint: &Joint) -> Result<(), UrdfParseError> {
+    let sdf_type = match joint.joint_type.as_str() {
+        "revolute" | "continuous" => "revolute",
+        "prismatic" => "prismatic",
+        "fixed" => "fixed",
+        other => {
+            return Err(UrdfParseError::InvalidStructure(format!(
+                "joint '{}' has type '{}', which has no SDF equivalent", joint.name, other
+            )));
+        }
+    };
+    
+    let mut element = BytesStart::new("joint");
+    element.push_attribute(("name", joint.name.as_str()));
+    element.push_attribute(("type", sdf_type));
+    writer.write_event(Event::Start(element))?;
+    
+    write_pose(writer, joint.origin.as_ref(), Some(&joint.parent))?;
+    write_text(writer, "parent", &joint.parent)?;
+    write_text(writer, "child", &joint.child)?;
+    
+    if sdf_type != "fixed" {
+        writer.write_event(Event::Start(BytesStart::new("axis")))?;
+        let xyz = joint.axis.as_ref().map(|axis| axis.xyz).unwrap_or([1.0, 0.0, 0.0]);
+        write_text(writer, "xyz", &format_values(&xyz))?;
+        
+        let limit = joint.limit.as_ref();
+        let (lower, upper) = if joint.joint_type == "continuous" {
+            (Some(-CONTINUOUS_LIMIT), Some(CONTINUOUS_LIMIT))
+        } else {
+            (limit.and_then(|limit| limit.lower), limit.and_then(|limit| limit.upper))
+        };
+        let effort = limit.and_then(|limit| limit.effort);
+        let velocity = limit.and_then(|limit| limit.velocity);
+        
+        if lower.is_some() || upper.is_some() || effort.is_some() || velocity.is_some() {
+            writer.write_event(Event::Start(BytesStart::new("limit")))?;
+            for (name, value) in [("lower", lower), ("upper", upper), ("effort", effort), ("velocity", velocity)] {
+                if let Some(value) = value {
+                    write_text(writer, name, &value.to_string())?;
+                }
+            }
+            writer.write_event(Event::End(BytesEnd::new("limit")))?;
+        }
+        
+        if let Some(dynamics) = &joint.dynamics {
+            writer.write_event(Event::Start(BytesStart::new("dynamics")))?;
+            if let Some(damping) = dynamics.damping {
+                write_text(writer, "damping", &damping.to_string())?;
+            }
+            if let Some(friction) = dynamics.friction {
+                write_text(writer, "friction", &friction.to_string())?;
+            }
+            writer.write_event(Event::End(BytesEnd::new("dynamics")))?;
+        }
+        
+        writer.write_event(Event::End(BytesEnd::new("axis")))?;
+    }
+    
+    writer.write_event(Event::End(BytesEnd::new("joint")))?;
+    Ok(())
+}
+
+fn write_pose(writer: &mut Writer<Vec<u8>>, origin: Option<&Origin>, relative_to: Option<&str>) -> Result<(), UrdfParseError> {
+    if origin.is_none() && relative_to.is_none() {
+        return Ok(());
+    }
+    
+    let (xyz, rpy) = origin.map(|origin| (origin.xyz, origin.rpy)).unwrap_or(([0.0; 3], [0.0; 3]));
+    let mut element = BytesStart::new("pose");
+    if let Some(frame) = relative_to {
+        element.push_attribute(("relative_to", frame));
+    }
+    
+    writer.write_event(Event::Start(element))?;
+    writer.write_event(Event::Text(BytesText::new(&format!("{} {}", format_values(&xyz), format_values(&rpy)))))?;
+    writer.write_event(Event::End(BytesEnd::new("pose")))?;
+    Ok(())
+}
+
+fn write_text(writer: &mut Writer<Vec<u8>>, tag: &str, text: &str) -> Result<(), UrdfParseError> {
+    writer.write_event(Event::Start(BytesStart::new(tag)))?;
+    writer.write_event(Event::Text(BytesText::new(text)))?;
+    writer.write_event(Event::End(BytesEnd::new(tag)))?;
+    Ok(())
+}
+
+fn format_values(values: &[f64]) -> String {
+    values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(" ")
+}
\ No newline at end of file
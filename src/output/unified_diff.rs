@@ -0,0 +1,210 @@
+const CONTEXT: usize = 3;
+
+enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let lines = diff_lines(&old_lines, &new_lines);
+    
+    let changes: Vec<usize> = lines.iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(index, _)| index)
+        .collect();
+    
+    if changes.is_empty() {
+        return String::new();
+    }
+    
+    let mut positions = Vec::with_capacity(lines.len());
+    let (mut old_line, mut new_line) = (0, 0);
+    for line in &lines {
+        positions.push((old_line, new_line));
+        match line {
+            DiffLine::Same(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            DiffLine::Removed(_) => old_line += 1,
+            DiffLine::Added(_) => new_line += 1,
+        }
+    }
+    
+    let mut output = format!("--- {}\n+++ {}\n", old_label, new_label);
+    let mut index = 0;
+    while index < changes.len() {
+        let start = changes[index].saturating_sub(CONTEXT);
+        let mut last_change = changes[index];
+        while index + 1 < changes.len() && changes[index + 1] <= last_change + 2 * CONTEXT + 1 {
+            index += 1;
+            last_change = changes[index];
+        }
+        index += 1;
+        let end = (last_change + CONTEXT + 1).min(lines.len());
+        
+        let hunk = &lines[start..end];
+        let (old_start, new_start) = positions[start];
+        let old_len = hunk.iter().filter(|line| !matches!(line, DiffLine::Added(_))).count();
+        let new_len = hunk.iter().filter(|line| !matches!(line, DiffLine::Removed(_))).count();
+        
+        output.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            if old_len == 0 { old_start } else { old_start + 1 },
+            old_len,
+            if new_len == 0 { new_start } else { new_start + 1 },
+            new_len,
+        ));
+        for line in hunk {
+            match line {
+                DiffLine::Same(text) => output.push_str(&format!(" {}\n", text)),
+                DiffLine::Removed(text) => output.push_str(&format!("-{}\n", text)),
+                DiffLine::Added(text) => output.push_str(&format!("+{}\n", text)),
+            }
+        }
+    }
+    
+    output
+}
+
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    diff_range(old, new, &mut lines);
+    lines
+}
+
+// Myers' linear-space diff: trim the common ends, then split on the middle snake and recurse.
+// Memory stays linear, which matters for `fix --dry-run` on generated files with many thousands of lines.
+fn diff_range<'a>(old: &[&'a str], new: &[&'a str], lines: &mut Vec<DiffLine<'a>>) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let (old_rest, new_rest) = (&old[prefix..], &new[prefix..]);
+    let suffix = old_rest.iter().rev().zip(new_rest.iter().rev()).take_while(|(a, b)| a == b).count();
+    let (old_middle, new_middle) = (&old_rest[..old_rest.len() - suffix], &new_rest[..new_rest.len() - suffix]);
+    
+    lines.extend(old[..prefix].iter().map(|text| DiffLine::Same(text)));
+    if old_middle.is_empty() || new_middle.is_empty() {
+        lines.extend(old_middle.iter().map(|text| DiffLine::Removed(text)));
+        lines.extend(new_middle.iter().map(|text| DiffLine::Added(text)));
+    } else {
+        let (x_start, y_start, x_end, y_end) = middle_snake(old_middle, new_middle);
+        diff_range(&old_middle[..x_start], &new_middle[..y_start], lines);
+        lines.extend(old_middle[x_start..x_end].iter().map(|text| DiffLine::Same(text)));
+        diff_range(&old_middle[x_end..], &new_middle[y_end..], lines);
+    }
+    lines.extend(old_rest[old_rest.len() - suffix..].iter().map(|text| DiffLine::Same(text)));
+}
+
+// Runs the forward and backward searches until they overlap and returns the snake where they meet.
+// The caller has trimmed the common ends, so both sides are non-empty and differ in their first and last lines.
+fn middle_snake(old: &[&str], new: &[&str]) -> (usize, usize, usize, usize) {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let max = (n + m + 1) / 2;
+    let offset = max + 1;
+    let mut forward = vec![0isize; (2 * max + 3) as usize];
+    let mut backward = vec![0isize; (2 * max + 3) as usize];
+    let at = |k: isize| (k + offset) as usize;
+    
+    for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && forward[at(k - 1)] < forward[at(k + 1)]) {
+                forward[at(k + 1)]
+            } else {
+                forward[at(k - 1)] + 1
+            };
+            let (x_start, y_start) = (x, x - k);
+            while x < n && x - k < m && old[x as usize] == new[(x - k) as usize] {
+                x += 1;
+            }
+            forward[at(k)] = x;
+            let c = delta - k;
+            if odd && (-(d - 1)..=d - 1).contains(&c) && x + backward[at(c)] >= n {
+                return (x_start as usize, y_start as usize, x as usize, (x - k) as usize);
+            }
+        }
+        
+        for c in (-d..=d).step_by(2) {
+            let mut u = if c == -d || (c != d && backward[at(c - 1)] < backward[at(c + 1)]) {
+                backward[at(c + 1)]
+            } else {
+                backward[at(c - 1)] + 1
+            };
+            let (u_start, v_start) = (u, u - c);
+            while u < n && u - c < m && old[(n - u - 1) as usize] == new[(m - (u - c) - 1) as usize] {
+                u += 1;
+            }
+            backward[at(c)] = u;
+            let k = delta - c;
+            if !odd && (-d..=d).contains(&k) && u + forward[at(k)] >= n {
+                return ((n - u) as usize, (m - (u - c)) as usize, (n - u_start) as usize, (m - v_start) as usize);
+            }
+        }
+    }
+    
+    unreachable!("the forward and backward searches always meet by d = (n + m + 1) / 2")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lcs_len(old: &[&str], new: &[&str]) -> usize {
+        let mut previous = vec![0; new.len() + 1];
+        for a in old {
+            let mut current = vec![0; new.len() + 1];
+            for (j, b) in new.iter().enumerate() {
+                current[j + 1] = if a == b { previous[j] + 1 } else { previous[j + 1].max(current[j]) };
+            }
+            previous = current;
+        }
+        previous[new.len()]
+    }
+
+    #[test]
+    fn diff_is_minimal_and_rebuilds_both_sides() {
+        let mut seed = 7u32;
+        let mut next = move || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 16) as usize
+        };
+        let words = ["a", "b", "c", "d"];
+        for _ in 0..300 {
+            let old: Vec<&str> = (0..next() % 12).map(|_| words[next() % 4]).collect();
+            let new: Vec<&str> = (0..next() % 12).map(|_| words[next() % 4]).collect();
+            let lines = diff_lines(&old, &new);
+            
+            let rebuilt_old: Vec<&str> = lines.iter().filter_map(|line| match line {
+                DiffLine::Same(text) | DiffLine::Removed(text) => Some(*text),
+                DiffLine::Added(_) => None,
+            }).collect();
+            let rebuilt_new: Vec<&str> = lines.iter().filter_map(|line| match line {
+                DiffLine::Same(text) | DiffLine::Added(text) => Some(*text),
+                DiffLine::Removed(_) => None,
+            }).collect();
+            assert_eq!((&rebuilt_old, &rebuilt_new), (&old, &new));
+            
+            let same = lines.iter().filter(|line| matches!(line, DiffLine::Same(_))).count();
+            assert_eq!(same, lcs_len(&old, &new), "{:?} -> {:?}", old, new);
+        }
+    }
+
+    #[test]
+    fn large_inputs_diff_in_linear_space() {
+        let old: Vec<String> = (0..40_000).map(|index| format!("  <link name=\"l{}\"/>", index)).collect();
+        let mut new = old.clone();
+        new[10] = "  <link name=\"renamed\"/>".to_string();
+        new.insert(30_000, "  <link name=\"extra\"/>".to_string());
+        new.remove(20_000);
+        
+        let diff = unified_diff(&old.join("\n"), &new.join("\n"), "a", "b");
+        assert_eq!(diff.lines().filter(|line| line.starts_with("@@")).count(), 3);
+        assert!(diff.contains("-  <link name=\"l10\"/>\n+  <link name=\"renamed\"/>"));
+        assert!(diff.contains("-  <link name=\"l20000\"/>"));
+        assert!(diff.contains("+  <link name=\"extra\"/>"));
+    }
+}
\ No newline at end of file
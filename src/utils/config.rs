@@ -0,0 +1,212 @@
+use crate::utils::modifier::{FixOptions, FormatOptions, SortMode};
+use crate::utils::processor::{IssueSeverity, LintOptions};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+pub const CONFIG_FILE_NAME: &str = ".urdfixrc";
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("cannot read {0}: {1}")]
+    Io(String, std::io::Error),
+    #[error("invalid config {0}: {1}")]
+    Parse(String, toml::de::Error),
+    #[error("cannot serialize config: {0}")]
+    Serialize(#[from] toml::ser::Error),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UrdfixConfig {
+    pub lint: LintConfig,
+    pub fix: FixConfig,
+    pub format: FormatConfig,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    pub collision_mesh_severity: Option<IssueSeverity>,
+    pub detect_swapped_origins: Option<bool>,
+    pub check_link_element_order: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FixConfig {
+    pub remove_duplicates: Option<bool>,
+    pub fix_naming: Option<bool>,
+    pub add_missing_properties: Option<bool>,
+    pub clean_whitespace: Option<bool>,
+    pub sort_elements: Option<bool>,
+    pub sort_mode: Option<SortMode>,
+    pub remove_unused_materials: Option<bool>,
+    pub merge_identical_materials: Option<bool>,
+    pub resolve_color_names: Option<bool>,
+    pub remove_orphaned_links: Option<bool>,
+    pub normalize_axes: Option<bool>,
+    pub strip_fixed_joint_extras: Option<bool>,
+    pub default_mass: Option<f64>,
+    pub relabel_pattern: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FormatConfig {
+    pub indent: Option<String>,
+    pub attribute_order: Option<Vec<String>>,
+    pub element_order: Option<Vec<String>>,
+    pub link_element_order: Option<Vec<String>>,
+    pub compact_empty_elements: Option<bool>,
+    pub max_line_length: Option<usize>,
+    pub minify: Option<bool>,
+    pub preserve_comments: Option<bool>,
+    pub interleave_joints: Option<bool>,
+    pub float_precision: Option<usize>,
+    pub emit_xml_declaration: Option<bool>,
+}
+
+impl UrdfixConfig {
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let display = path.display().to_string();
+        let content = fs::read_to_string(path).map_err(|e| ConfigError::Io(display.clone(), e))?;
+        toml::from_str(&content).map_err(|e| ConfigError::Parse(display, e))
+    }
+
+    pub fn discover(target: &Path) -> Option<PathBuf> {
+        let start = target.canonicalize().ok()?;
+        start.ancestors()
+            .skip(1)
+            .map(|dir| dir.join(CONFIG_FILE_NAME))
+            .find(|candidate| candidate.is_file())
+    }
+
+    pub fn for_target(target: &Path, explicit: Option<&Path>) -> Result<Self, ConfigError> {
+        match explicit.map(Path::to_path_buf).or_else(|| Self::discover(target)) {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn lint_options(&self) -> LintOptions {
+        let defaults = LintOptions::default();
+        let lint = &self.lint;
+        LintOptions {
+            collision_mesh_severity: lint.collision_mesh_severity.clone().unwrap_or(defaults.collision_mesh_severity),
+            detect_swapped_origins: lint.detect_swapped_origins.unwrap_or(defaults.detect_swapped_origins),
+            check_link_element_order: lint.check_link_element_order.unwrap_or(defaults.check_link_element_order),
+            ..defaults
+        }
+    }
+
+    pub fn fix_options(&self) -> FixOptions {
+        let defaults = FixOptions::default();
+        let fix = &self.fix;
+        FixOptions {
+            remove_duplicates: fix.remove_duplicates.unwrap_or(defaults.remove_duplicates),
+            fix_naming: fix.fix_naming.unwrap_or(defaults.fix_naming),
+            add_missing_properties: fix.add_missing_properties.unwrap_or(defaults.add_missing_properties),
+            clean_whitespace: fix.clean_whitespace.unwrap_or(defaults.clean_whitespace),
+            sort_elements: fix.sort_elements.unwrap_or(defaults.sort_elements),
+            sort_mode: fix.sort_mode.unwrap_or(defaults.sort_mode),
+            remove_unused_materials: fix.remove_unused_materials.unwrap_or(defaults.remove_unused_materials),
+            merge_identical_materials: fix.merge_identical_materials.unwrap_or(defaults.merge_identical_materials),
+            resolve_color_names: fix.resolve_color_names.unwrap_or(defaults.resolve_color_names),
+            remove_orphaned_links: fix.remove_orphaned_links.unwrap_or(defaults.remove_orphaned_links),
+            normalize_axes: fix.normalize_axes.unwrap_or(defaults.normalize_axes),
+            strip_fixed_joint_extras: fix.strip_fixed_joint_extras.unwrap_or(defaults.strip_fixed_joint_extras),
+            default_mass: fix.default_mass.unwrap_or(defaults.default_mass),
+            relabel_pattern: fix.relabel_pattern.clone().or(defaults.relabel_pattern),
+        }
+    }
+
+    pub fn format_options(&self) -> FormatOptions {
+        let defaults = FormatOptions::default();
+        let format = &self.format;
+        FormatOptions {
+            indent: format.indent.clone().unwrap_or(defaults.indent),
+            attribute_order: format.attribute_order.clone().unwrap_or(defaults.attribute_order),
+            element_order: format.element_order.clone().unwrap_or(defaults.element_order),
+            link_element_order: format.link_element_order.clone().unwrap_or(defaults.link_element_order),
+            compact_empty_elements: format.compact_empty_elements.unwrap_or(defaults.compact_empty_elements),
+            max_line_length: match format.max_line_length {
+                Some(0) => None,
+                Some(length) => Some(length),
+                None => defaults.max_line_length,
+            },
+            minify: format.minify.unwrap_or(defaults.minify),
+            preserve_comments: format.preserve_comments.unwrap_or(defaults.preserve_comments),
+            interleave_joints: format.interleave_joints.unwrap_or(defaults.interleave_joints),
+            float_precision: format.float_precision.or(defaults.float_precision),
+            emit_xml_declaration: format.emit_xml_declaration.unwrap_or(defaults.emit_xml_declaration),
+        }
+    }
+
+    pub fn from_options(fix: &FixOptions, format: &FormatOptions) -> Self {
+        Self {
+            lint: LintConfig::default(),
+            fix: FixConfig {
+                remove_duplicates: Some(fix.remove_duplicates),
+                fix_naming: Some(fix.fix_naming),
+                add_missing_properties: Some(fix.add_missing_properties),
+                clean_whitespace: Some(fix.clean_whitespace),
+                sort_elements: Some(fix.sort_elements),
+                sort_mode: Some(fix.sort_mode),
+                remove_unused_materials: Some(fix.remove_unused_materials),
+                merge_identical_materials: Some(fix.merge_identical_materials),
+                resolve_color_names: Some(fix.resolve_color_names),
+                remove_orphaned_links: Some(fix.remove_orphaned_links),
+                normalize_axes: Some(fix.normalize_axes),
+                strip_fixed_joint_extras: Some(fix.strip_fixed_joint_extras),
+                default_mass: Some(fix.default_mass),
+                relabel_pattern: fix.relabel_pattern.clone(),
+            },
+            format: FormatConfig {
+                indent: Some(format.indent.clone()),
+                attribute_order: Some(format.attribute_order.clone()),
+                element_order: Some(format.element_order.clone()),
+                link_element_order: Some(format.link_element_order.clone()),
+                compact_empty_elements: Some(format.compact_empty_elements),
+                max_line_length: Some(format.max_line_length.unwrap_or(0)),
+                minify: Some(format.minify),
+                preserve_comments: Some(format.preserve_comments),
+                interleave_joints: Some(format.interleave_joints),
+                float_precision: format.float_precision,
+                emit_xml_declaration: Some(format.emit_xml_declaration),
+            },
+        }
+    }
+
+    pub fn to_toml(&self) -> Result<String, ConfigError> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lint_section_sets_lint_options() {
+        let config: UrdfixConfig = toml::from_str("[lint]\ncollision_mesh_severity = \"error\"\ndetect_swapped_origins = true\ncheck_link_element_order = true\n").unwrap();
+        let options = config.lint_options();
+        assert_eq!(options.collision_mesh_severity, IssueSeverity::Error);
+        assert!(options.detect_swapped_origins);
+        assert!(options.check_link_element_order);
+        
+        let defaults = UrdfixConfig::default().lint_options();
+        assert_eq!(defaults.collision_mesh_severity, IssueSeverity::Warning);
+        assert!(!defaults.detect_swapped_origins);
+        assert!(!defaults.check_link_element_order);
+    }
+
+    #[test]
+    fn axis_normalization_is_opt_in() {
+        assert!(!UrdfixConfig::default().fix_options().normalize_axes);
+        
+        let config: UrdfixConfig = toml::from_str("[fix]\nnormalize_axes = true\n").unwrap();
+        assert!(config.fix_options().normalize_axes);
+    }
+}
\ No newline at end of file
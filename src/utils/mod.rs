@@ -1,7 +1,13 @@
+pub mod config;
 pub mod parser;
 pub mod processor;
 pub mod modifier;
+pub mod resolver;
+pub mod transform;
+pub mod xacro;
 
+pub use config::{UrdfixConfig, ConfigError};
 pub use parser::*;
 pub use processor::*;
-pub use modifier::*;
\ No newline at end of file
+pub use modifier::*;
+pub use resolver::MeshResolver;
\ No newline at end of file
@@ -1,12 +1,48 @@
-use crate::utils::parser::{UrdfDocument, Robot, Link, Joint, Material, UrdfParseError};
-use crate::utils::processor::{UrdfProcessor, UrdfIssue, IssueSeverity};
-use quick_xml::{Writer, events::Event, name::QName, events::BytesStart};
+use crate::utils::parser::{UrdfDocument, Robot, CommentAnchor, Link, Joint, Material, Color, UrdfParseError, Inertial, Inertia, Visual, Collision, Origin, Geometry, GeometryShape};
+use crate::utils::processor::{UrdfProcessor, UrdfIssue, IssueSeverity, LINK_ELEMENT_ORDER, AXIS_NORM_TOLERANCE};
+use quick_xml::{Reader, Writer, events::Event, events::BytesDecl, events::BytesStart, events::BytesText};
 use std::io::Cursor;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 
 pub struct UrdfModifier;
 
+const DEFAULT_AXIS: [f64; 3] = [1.0, 0.0, 0.0];
+
+const DEFAULT_INERTIA_SIDE: f64 = 0.1;
+
+pub const ROBOT_ELEMENT_ORDER: [&str; 5] = ["material", "link", "joint", "gazebo", "transmission"];
+
+pub const NAMED_COLORS: [(&str, [f64; 4]); 18] = [
+    ("black", [0.0, 0.0, 0.0, 1.0]),
+    ("white", [1.0, 1.0, 1.0, 1.0]),
+    ("red", [1.0, 0.0, 0.0, 1.0]),
+    ("green", [0.0, 0.5, 0.0, 1.0]),
+    ("lime", [0.0, 1.0, 0.0, 1.0]),
+    ("blue", [0.0, 0.0, 1.0, 1.0]),
+    ("yellow", [1.0, 1.0, 0.0, 1.0]),
+    ("cyan", [0.0, 1.0, 1.0, 1.0]),
+    ("magenta", [1.0, 0.0, 1.0, 1.0]),
+    ("gray", [0.5, 0.5, 0.5, 1.0]),
+    ("grey", [0.5, 0.5, 0.5, 1.0]),
+    ("lightgray", [0.83, 0.83, 0.83, 1.0]),
+    ("lightgrey", [0.83, 0.83, 0.83, 1.0]),
+    ("silver", [0.75, 0.75, 0.75, 1.0]),
+    ("orange", [1.0, 0.65, 0.0, 1.0]),
+    ("brown", [0.65, 0.16, 0.16, 1.0]),
+    ("purple", [0.5, 0.0, 0.5, 1.0]),
+    ("pink", [1.0, 0.75, 0.8, 1.0]),
+];
+
+pub fn named_color(name: &str) -> Option<[f64; 4]> {
+    let key: String = name.chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect();
+    NAMED_COLORS.iter().find(|(color, _)| *color == key).map(|(_, rgba)| *rgba)
+}
+
 #[derive(Debug, Clone)]
 pub struct FixOptions {
     pub remove_duplicates: bool,
@@ -14,7 +50,15 @@ pub struct FixOptions {
     pub add_missing_properties: bool,
     pub clean_whitespace: bool,
     pub sort_elements: bool,
+    pub sort_mode: SortMode,
     pub remove_unused_materials: bool,
+    pub merge_identical_materials: bool,
+    pub resolve_color_names: bool,
+    pub remove_orphaned_links: bool,
+    pub normalize_axes: bool,
+    pub strip_fixed_joint_extras: bool,
+    pub default_mass: f64,
+    pub relabel_pattern: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -22,8 +66,21 @@ pub struct FormatOptions {
     pub indent: String,
     pub attribute_order: Vec<String>,
     pub element_order: Vec<String>,
+    pub link_element_order: Vec<String>,
     pub compact_empty_elements: bool,
     pub max_line_length: Option<usize>,
+    pub minify: bool,
+    pub preserve_comments: bool,
+    pub interleave_joints: bool,
+    pub float_precision: Option<usize>,
+    pub emit_xml_declaration: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortMode {
+    Alphabetical,
+    Topological,
 }
 
 impl Default for FixOptions {
@@ -34,7 +91,15 @@ impl Default for FixOptions {
             add_missing_properties: false,
             clean_whitespace: true,
             sort_elements: false,
+            sort_mode: SortMode::Alphabetical,
             remove_unused_materials: true,
+            merge_identical_materials: false,
+            resolve_color_names: false,
+            remove_orphaned_links: false,
+            normalize_axes: false,
+            strip_fixed_joint_extras: false,
+            default_mass: 1.0,
+            relabel_pattern: None,
         }
     }
 }
@@ -51,15 +116,15 @@ impl Default for FormatOptions {
                 "xyz".to_string(),
                 "rpy".to_string(),
             ],
-            element_order: vec![
-                "material".to_string(),
-                "link".to_string(),
-                "joint".to_string(),
-                "gazebo".to_string(),
-                "transmission".to_string(),
-            ],
+            element_order: ROBOT_ELEMENT_ORDER.iter().map(|tag| tag.to_string()).collect(),
+            link_element_order: LINK_ELEMENT_ORDER.iter().map(|tag| tag.to_string()).collect(),
             compact_empty_elements: true,
             max_line_length: Some(120),
+            minify: false,
+            preserve_comments: true,
+            interleave_joints: false,
+            float_precision: None,
+            emit_xml_declaration: true,
         }
     }
 }
@@ -72,24 +137,52 @@ impl UrdfModifier {
             changes.extend(self.remove_duplicates(&mut doc.robot)?);
         }
         
+        if options.resolve_color_names {
+            changes.extend(self.resolve_color_names(doc)?);
+        }
+        
+        if options.merge_identical_materials {
+            changes.extend(self.merge_identical_materials(&mut doc.robot)?);
+        }
+        
         if options.remove_unused_materials {
             changes.extend(self.remove_unused_materials(&mut doc.robot)?);
         }
         
+        if options.remove_orphaned_links {
+            changes.extend(self.remove_orphaned_links(doc)?);
+        }
+        
         if options.fix_naming {
             changes.extend(self.fix_naming_conventions(&mut doc.robot)?);
         }
         
+        if let Some(pattern) = &options.relabel_pattern {
+            changes.extend(self.relabel(&mut doc.robot, pattern)?);
+        }
+        
+        if options.normalize_axes {
+            changes.extend(self.normalize_axes(&mut doc.robot)?);
+        }
+        
+        if options.strip_fixed_joint_extras {
+            changes.extend(self.strip_fixed_joint_extras(&mut doc.robot)?);
+        }
+        
         if options.add_missing_properties {
-            changes.extend(self.add_missing_properties(&mut doc.robot)?);
+            changes.extend(self.add_missing_properties(&mut doc.robot, options.default_mass)?);
         }
         
         if options.sort_elements {
-            changes.extend(self.sort_elements(&mut doc.robot)?);
+            changes.extend(self.sort_elements(&mut doc.robot, options.sort_mode)?);
         }
         
         if options.clean_whitespace {
-            self.regenerate_xml(doc)?;
+            let format = FormatOptions {
+                interleave_joints: options.sort_elements && options.sort_mode == SortMode::Topological,
+                ..FormatOptions::default()
+            };
+            self.regenerate_xml_with_formatting(doc, &format)?;
             changes.push("Cleaned whitespace and formatting".to_string());
         }
         
@@ -122,52 +215,158 @@ impl UrdfModifier {
         Ok(changes)
     }
 
-    pub fn remove_element(&self, doc: &mut UrdfDocument, element_type: &str, name: &str) -> Result<bool, UrdfParseError> {
-        match element_type {
-            "link" => {
-                if doc.robot.links.remove(name).is_some() {
-                    self.regenerate_xml(doc)?;
-                    Ok(true)
-                } else {
-                    Ok(false)
+    pub fn extract_subtree(&self, doc: &UrdfDocument, root: &str) -> Result<UrdfDocument, UrdfParseError> {
+        if !doc.robot.links.contains_key(root) {
+            return Err(UrdfParseError::InvalidStructure(format!("Link '{}' does not exist", root)));
+        }
+        
+        let graph = UrdfProcessor.get_dependency_graph(doc);
+        let mut included = HashSet::from([root.to_string()]);
+        let mut stack = vec![root.to_string()];
+        while let Some(link_name) = stack.pop() {
+            for child in graph.get(&link_name).into_iter().flatten() {
+                if included.insert(child.clone()) {
+                    stack.push(child.clone());
                 }
             }
-            "joint" => {
-                if doc.robot.joints.remove(name).is_some() {
-                    self.regenerate_xml(doc)?;
-                    Ok(true)
-                } else {
-                    Ok(false)
-                }
+        }
+        
+        let robot = &doc.robot;
+        let links: IndexMap<String, Link> = robot.links.iter()
+            .filter(|(name, _)| included.contains(*name))
+            .map(|(name, link)| (name.clone(), link.clone()))
+            .collect();
+        let joints: IndexMap<String, Joint> = robot.joints.iter()
+            .filter(|(_, joint)| included.contains(&joint.parent) && included.contains(&joint.child) && joint.child != root)
+            .map(|(name, joint)| (name.clone(), joint.clone()))
+            .collect();
+        let used_materials: HashSet<&str> = links.values()
+            .flat_map(|link| link.visual.iter().filter_map(|visual| visual.material.as_ref()))
+            .map(|material| material.name.as_str())
+            .collect();
+        let materials: IndexMap<String, Material> = robot.materials.iter()
+            .filter(|(name, _)| used_materials.contains(name.as_str()))
+            .map(|(name, material)| (name.clone(), material.clone()))
+            .collect();
+        let kept = |name: &str| links.contains_key(name) || joints.contains_key(name) || materials.contains_key(name);
+        
+        let mut subtree = UrdfDocument {
+            robot: Robot {
+                name: robot.name.clone(),
+                attributes: robot.attributes.clone(),
+                gazebo_elements: robot.gazebo_elements.iter()
+                    .filter(|gazebo| gazebo.reference.as_deref().is_some_and(kept))
+                    .cloned()
+                    .collect(),
+                transmission_elements: Vec::new(),
+                duplicates: Vec::new(),
+                comments: robot.comments.iter()
+                    .filter(|comment| match &comment.anchor {
+                        CommentAnchor::Before { name, .. } => kept(name),
+                        _ => true,
+                    })
+                    .cloned()
+                    .collect(),
+                links,
+                joints,
+                materials,
+                declaration: robot.declaration.clone(),
+            },
+            raw_xml: String::new(),
+            warnings: Vec::new(),
+            suppressions: Vec::new(),
+        };
+        self.regenerate_xml(&mut subtree)?;
+        
+        Ok(subtree)
+    }
+
+    pub fn prefix_names(&self, doc: &mut UrdfDocument, prefix: &str) -> Result<(), UrdfParseError> {
+        let prefixed = |kind: &str, names: Vec<&String>| -> Result<HashMap<String, String>, UrdfParseError> {
+            let renames: HashMap<String, String> = names.iter()
+                .map(|name| (name.to_string(), format!("{}{}", prefix, name)))
+                .collect();
+            match names.iter().find(|name| renames.contains_key(&format!("{}{}", prefix, name))) {
+                Some(name) => Err(UrdfParseError::InvalidStructure(format!(
+                    "Prefixing {} '{}' would collide with existing {} '{}{}'", kind, name, kind, prefix, name
+                ))),
+                None => Ok(renames),
             }
-            "material" => {
-                if doc.robot.materials.remove(name).is_some() {
-                    self.regenerate_xml(doc)?;
-                    Ok(true)
-                } else {
-                    Ok(false)
+        };
+        let link_renames = prefixed("link", doc.robot.links.keys().collect())?;
+        let joint_renames = prefixed("joint", doc.robot.joints.keys().collect())?;
+        let material_renames = prefixed("material", doc.robot.materials.keys().collect())?;
+        
+        self.rename_links(&mut doc.robot, &link_renames);
+        self.rename_joints(&mut doc.robot, &joint_renames)?;
+        self.rename_materials(&mut doc.robot, &material_renames);
+        self.regenerate_xml(doc)
+    }
+
+    pub fn merge_documents(&self, doc: &mut UrdfDocument, other: UrdfDocument, attach_to: Option<&str>) -> Result<Vec<String>, UrdfParseError> {
+        let mut changes = Vec::new();
+        let other_root = match attach_to {
+            Some(link) => {
+                if !doc.robot.links.contains_key(link) {
+                    return Err(UrdfParseError::InvalidStructure(format!("Link '{}' does not exist in the first robot", link)));
+                }
+                match UrdfProcessor.find_root_links(&other).as_slice() {
+                    [root] => Some(root.clone()),
+                    roots => return Err(UrdfParseError::InvalidStructure(format!("The second robot needs exactly one root link to attach, found {}: {:?}", roots.len(), roots))),
                 }
             }
-            _ => Ok(false),
+            None => None,
+        };
+        
+        let mut collisions = Vec::new();
+        collisions.extend(other.robot.links.keys().filter(|name| doc.robot.links.contains_key(*name)).map(|name| format!("link '{}'", name)));
+        collisions.extend(other.robot.joints.keys().filter(|name| doc.robot.joints.contains_key(*name)).map(|name| format!("joint '{}'", name)));
+        collisions.extend(other.robot.materials.iter()
+            .filter(|(name, material)| doc.robot.materials.get(*name).is_some_and(|existing| existing != *material))
+            .map(|(name, _)| format!("material '{}'", name)));
+        if !collisions.is_empty() {
+            return Err(UrdfParseError::InvalidStructure(format!("Name collisions between the robots: {}", collisions.join(", "))));
         }
+        
+        let other = other.robot;
+        changes.push(format!("Merged {} link(s) and {} joint(s) from '{}'", other.links.len(), other.joints.len(), other.name));
+        doc.robot.links.extend(other.links);
+        doc.robot.joints.extend(other.joints);
+        for (name, material) in other.materials {
+            doc.robot.materials.entry(name).or_insert(material);
+        }
+        doc.robot.gazebo_elements.extend(other.gazebo_elements);
+        doc.robot.transmission_elements.extend(other.transmission_elements);
+        doc.robot.comments.extend(other.comments.into_iter().filter(|comment| comment.anchor != CommentAnchor::Document));
+        
+        if let (Some(parent), Some(child)) = (attach_to, other_root) {
+            let mut name = format!("{}_to_{}", parent, child);
+            while doc.robot.joints.contains_key(&name) {
+                name.push('_');
+            }
+            changes.push(format!("Attached '{}' to '{}' with fixed joint '{}'", child, parent, name));
+            doc.robot.joints.insert(name.clone(), Joint {
+                name,
+                joint_type: "fixed".to_string(),
+                attributes: IndexMap::new(),
+                parent: parent.to_string(),
+                child,
+                origin: None,
+                axis: None,
+                limit: None,
+                dynamics: None,
+                mimic: None,
+            });
+        }
+        
+        self.regenerate_xml(doc)?;
+        Ok(changes)
     }
 
-    pub fn rename_element(&self, doc: &mut UrdfDocument, element_type: &str, old_name: &str, new_name: &str) -> Result<bool, UrdfParseError> {
+    pub fn remove_element(&self, doc: &mut UrdfDocument, element_type: &str, name: &str) -> Result<bool, UrdfParseError> {
         match element_type {
             "link" => {
-                if let Some(mut link) = doc.robot.links.remove(old_name) {
-                    link.name = new_name.to_string();
-                    
-                    for joint in doc.robot.joints.values_mut() {
-                        if joint.parent == old_name {
-                            joint.parent = new_name.to_string();
-                        }
-                        if joint.child == old_name {
-                            joint.child = new_name.to_string();
-                        }
-                    }
-                    
-                    doc.robot.links.insert(new_name.to_string(), link);
+                if doc.robot.links.shift_remove(name).is_some() {
                     self.regenerate_xml(doc)?;
                     Ok(true)
                 } else {
@@ -175,9 +374,7 @@ impl UrdfModifier {
                 }
             }
             "joint" => {
-                if let Some(mut joint) = doc.robot.joints.remove(old_name) {
-                    joint.name = new_name.to_string();
-                    doc.robot.joints.insert(new_name.to_string(), joint);
+                if doc.robot.joints.shift_remove(name).is_some() {
                     self.regenerate_xml(doc)?;
                     Ok(true)
                 } else {
@@ -185,20 +382,7 @@ impl UrdfModifier {
                 }
             }
             "material" => {
-                if let Some(mut material) = doc.robot.materials.remove(old_name) {
-                    material.name = new_name.to_string();
-                    
-                    for link in doc.robot.links.values_mut() {
-                        for visual in &mut link.visual {
-                            if let Some(material_ref) = &mut visual.material {
-                                if material_ref.name == old_name {
-                                    material_ref.name = new_name.to_string();
-                                }
-                            }
-                        }
-                    }
-                    
-                    doc.robot.materials.insert(new_name.to_string(), material);
+                if doc.robot.materials.shift_remove(name).is_some() {
                     self.regenerate_xml(doc)?;
                     Ok(true)
                 } else {
@@ -209,31 +393,28 @@ impl UrdfModifier {
         }
     }
 
-    fn remove_duplicates(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
-        let mut changes = Vec::new();
-        
-        let duplicate_links = self.find_duplicate_links(robot);
-        for link_name in duplicate_links {
-            robot.links.remove(&link_name);
-            changes.push(format!("Removed duplicate link: {}", link_name));
-        }
-        
-        let duplicate_joints = self.find_duplicate_joints(robot);
-        for joint_name in duplicate_joints {
-            robot.joints.remove(&joint_name);
-            changes.push(format!("Removed duplicate joint: {}", joint_name));
+    pub fn rename_element(&self, doc: &mut UrdfDocument, element_type: &str, old_name: &str, new_name: &str) -> Result<bool, UrdfParseError> {
+        let renames = HashMap::from([(old_name.to_string(), new_name.to_string())]);
+        match element_type {
+            "link" if doc.robot.links.contains_key(old_name) => self.rename_links(&mut doc.robot, &renames),
+            "joint" if doc.robot.joints.contains_key(old_name) => self.rename_joints(&mut doc.robot, &renames)?,
+            "material" if doc.robot.materials.contains_key(old_name) => self.rename_materials(&mut doc.robot, &renames),
+            _ => return Ok(false),
         }
         
-        let duplicate_materials = self.find_duplicate_materials(robot);
-        for material_name in duplicate_materials {
-            robot.materials.remove(&material_name);
-            changes.push(format!("Removed duplicate material: {}", material_name));
-        }
+        self.regenerate_xml(doc)?;
+        Ok(true)
+    }
+
+    pub fn remove_duplicates(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
+        let changes = robot.duplicates.drain(..)
+            .map(|duplicate| format!("Removed duplicate {}: {} (kept the last definition)", duplicate.kind, duplicate.name))
+            .collect();
         
         Ok(changes)
     }
 
-    fn remove_unused_materials(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
+    pub fn remove_unused_materials(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
         let mut changes = Vec::new();
         let mut used_materials = HashSet::new();
         
@@ -251,112 +432,537 @@ impl UrdfModifier {
             .collect();
         
         for material_name in unused_materials {
-            robot.materials.remove(&material_name);
+            robot.materials.shift_remove(&material_name);
             changes.push(format!("Removed unused material: {}", material_name));
         }
         
         Ok(changes)
     }
 
-    fn fix_naming_conventions(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
+    pub fn merge_identical_materials(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
+        let mut changes = Vec::new();
+        let mut canonical: Vec<&Material> = Vec::new();
+        let mut merges = Vec::new();
+        
+        for material in robot.materials.values() {
+            if material.color.is_none() && material.texture.is_none() {
+                continue;
+            }
+            match canonical.iter().find(|kept| kept.color == material.color && kept.texture == material.texture) {
+                Some(kept) => merges.push((material.name.clone(), kept.name.clone())),
+                None => canonical.push(material),
+            }
+        }
+        
+        for (duplicate, kept) in &merges {
+            robot.materials.shift_remove(duplicate);
+            changes.push(format!("Merged material {} into identical material {}", duplicate, kept));
+        }
+        
+        let renames: HashMap<String, String> = merges.into_iter().collect();
+        self.update_material_references(robot, &renames);
+        
+        Ok(changes)
+    }
+
+    pub fn resolve_color_names(&self, doc: &mut UrdfDocument) -> Result<Vec<String>, UrdfParseError> {
+        let mut changes = Vec::new();
+        
+        for material in doc.robot.materials.values_mut().filter(|material| material.color.is_none()) {
+            match named_color(&material.name) {
+                Some(rgba) => {
+                    material.color = Some(Color { rgba });
+                    changes.push(format!("Resolved color name {} to rgba {}", material.name, self.format_values(&rgba, &FormatOptions::default())));
+                }
+                None if material.texture.is_none() => {
+                    doc.warnings.push(format!("material '{}' has no color or texture and its name is not a known color", material.name));
+                }
+                None => {}
+            }
+        }
+        
+        Ok(changes)
+    }
+
+    pub fn extract_materials(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
+        let mut changes = Vec::new();
+        
+        for link in robot.links.values_mut() {
+            for material_ref in link.visual.iter_mut().filter_map(|visual| visual.material.as_mut()) {
+                if material_ref.color.is_none() && material_ref.texture.is_none() {
+                    continue;
+                }
+                let color = material_ref.color.take();
+                let texture = material_ref.texture.take();
+                
+                let identical = robot.materials.values()
+                    .find(|material| material.color == color && material.texture == texture)
+                    .map(|material| material.name.clone());
+                let name = match identical {
+                    Some(name) => {
+                        changes.push(format!("Replaced inline material {} in link {} with a reference to {}", material_ref.name, link.name, name));
+                        name
+                    }
+                    None => {
+                        let mut name = material_ref.name.clone();
+                        let mut suffix = 1;
+                        while robot.materials.contains_key(&name) {
+                            name = format!("{}_{}", material_ref.name, suffix);
+                            suffix += 1;
+                        }
+                        robot.materials.insert(name.clone(), Material { name: name.clone(), color, texture });
+                        changes.push(format!("Extracted inline material {} from link {}", name, link.name));
+                        name
+                    }
+                };
+                material_ref.name = name;
+            }
+        }
+        
+        Ok(changes)
+    }
+
+    pub fn inline_materials(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
+        let mut changes = Vec::new();
+        let mut inlined = HashSet::new();
+        
+        for link in robot.links.values_mut() {
+            for material_ref in link.visual.iter_mut().filter_map(|visual| visual.material.as_mut()) {
+                if material_ref.color.is_some() || material_ref.texture.is_some() {
+                    continue;
+                }
+                let Some(material) = robot.materials.get(&material_ref.name) else {
+                    continue;
+                };
+                if material.color.is_none() && material.texture.is_none() {
+                    continue;
+                }
+                material_ref.color = material.color.clone();
+                material_ref.texture = material.texture.clone();
+                inlined.insert(material.name.clone());
+            }
+        }
+        
+        let inlined: Vec<String> = robot.materials.keys()
+            .filter(|name| inlined.contains(*name))
+            .cloned()
+            .collect();
+        for name in inlined {
+            robot.materials.shift_remove(&name);
+            changes.push(format!("Inlined material {} into the visuals that use it", name));
+        }
+        
+        Ok(changes)
+    }
+
+    pub fn remove_orphaned_links(&self, doc: &mut UrdfDocument) -> Result<Vec<String>, UrdfParseError> {
         let mut changes = Vec::new();
         
+        for name in UrdfProcessor.find_orphaned_links(doc) {
+            doc.robot.links.shift_remove(&name);
+            changes.push(format!("Removed orphaned link: {}", name));
+        }
+        
+        Ok(changes)
+    }
+
+    pub fn fix_naming_conventions(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
+        let mut changes = Vec::new();
+        
+        if !self.is_valid_name(&robot.name) {
+            let new_name = self.fix_name(&robot.name);
+            if new_name != robot.name {
+                changes.push(format!("Fixed robot name: {} -> {}", robot.name, new_name));
+                robot.name = new_name;
+            }
+        }
+        
         let bad_link_names: Vec<String> = robot.links.keys()
             .filter(|name| !self.is_valid_name(name))
             .cloned()
             .collect();
         
+        let mut link_renames = HashMap::new();
         for old_name in bad_link_names {
             let new_name = self.fix_name(&old_name);
             if new_name != old_name {
-                if let Some(mut link) = robot.links.remove(&old_name) {
-                    link.name = new_name.clone();
-                    
-                    for joint in robot.joints.values_mut() {
-                        if joint.parent == old_name {
-                            joint.parent = new_name.clone();
-                        }
-                        if joint.child == old_name {
-                            joint.child = new_name.clone();
-                        }
-                    }
-                    
-                    robot.links.insert(new_name.clone(), link);
-                    changes.push(format!("Fixed link name: {} -> {}", old_name, new_name));
-                }
+                changes.push(format!("Fixed link name: {} -> {}", old_name, new_name));
+                link_renames.insert(old_name, new_name);
             }
         }
+        self.rename_links(robot, &link_renames);
         
         let bad_joint_names: Vec<String> = robot.joints.keys()
             .filter(|name| !self.is_valid_name(name))
             .cloned()
             .collect();
         
+        let mut joint_renames = HashMap::new();
         for old_name in bad_joint_names {
             let new_name = self.fix_name(&old_name);
             if new_name != old_name {
-                if let Some(mut joint) = robot.joints.remove(&old_name) {
-                    joint.name = new_name.clone();
-                    robot.joints.insert(new_name.clone(), joint);
-                    changes.push(format!("Fixed joint name: {} -> {}", old_name, new_name));
-                }
+                changes.push(format!("Fixed joint name: {} -> {}", old_name, new_name));
+                joint_renames.insert(old_name, new_name);
             }
         }
+        self.rename_joints(robot, &joint_renames)?;
         
         Ok(changes)
     }
 
-    fn add_missing_properties(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
+    pub fn relabel(&self, robot: &mut Robot, pattern: &str) -> Result<Vec<String>, UrdfParseError> {
+        let mut changes = Vec::new();
+        let (link_order, joint_order) = self.traversal_order(robot);
+        
+        let link_names = self.relabel_names(&link_order, "link", pattern)?;
+        let joint_names = self.relabel_names(&joint_order, "joint", pattern)?;
+        
+        self.rename_links(robot, &link_names);
+        self.rename_joints(robot, &joint_names)?;
+        
+        for old_name in &link_order {
+            if link_names[old_name] != *old_name {
+                changes.push(format!("Relabeled link: {} -> {}", old_name, link_names[old_name]));
+            }
+        }
+        for old_name in &joint_order {
+            if joint_names[old_name] != *old_name {
+                changes.push(format!("Relabeled joint: {} -> {}", old_name, joint_names[old_name]));
+            }
+        }
+        
+        Ok(changes)
+    }
+
+    pub fn normalize_axes(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
+        let mut changes = Vec::new();
+        
+        for (name, joint) in &mut robot.joints {
+            let Some(axis) = &mut joint.axis else {
+                continue;
+            };
+            
+            let norm = axis.xyz.iter().map(|value| value * value).sum::<f64>().sqrt();
+            if norm < AXIS_NORM_TOLERANCE || (norm - 1.0).abs() <= AXIS_NORM_TOLERANCE {
+                continue;
+            }
+            
+            let normalized = axis.xyz.map(|value| value / norm);
+            changes.push(format!("Normalized axis of joint {}: {:?} -> {:?}", name, axis.xyz, normalized));
+            axis.xyz = normalized;
+        }
+        
+        Ok(changes)
+    }
+
+    pub fn map_rpy(&self, robot: &mut Robot, convert: fn(f64) -> f64) -> usize {
+        let mut origins: Vec<&mut Origin> = Vec::new();
+        
+        for joint in robot.joints.values_mut() {
+            origins.extend(joint.origin.as_mut());
+        }
+        for link in robot.links.values_mut() {
+            origins.extend(link.inertial.as_mut().and_then(|i| i.origin.as_mut()));
+            origins.extend(link.visual.iter_mut().filter_map(|v| v.origin.as_mut()));
+            origins.extend(link.collision.iter_mut().filter_map(|c| c.origin.as_mut()));
+        }
+        
+        let count = origins.len();
+        for origin in origins {
+            origin.rpy = origin.rpy.map(convert);
+        }
+        count
+    }
+
+    pub fn strip_fixed_joint_extras(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
+        let mut changes = Vec::new();
+        
+        for (name, joint) in robot.joints.iter_mut().filter(|(_, joint)| joint.joint_type == "fixed") {
+            let removed: Vec<&str> = [
+                ("axis", joint.axis.take().is_some()),
+                ("limit", joint.limit.take().is_some()),
+                ("dynamics", joint.dynamics.take().is_some()),
+            ]
+            .into_iter()
+            .filter(|(_, removed)| *removed)
+            .map(|(element, _)| element)
+            .collect();
+            
+            if !removed.is_empty() {
+                changes.push(format!("Removed <{}> from fixed joint {}", removed.join(">, <"), name));
+            }
+        }
+        
+        Ok(changes)
+    }
+
+    pub fn add_missing_properties(&self, robot: &mut Robot, default_mass: f64) -> Result<Vec<String>, UrdfParseError> {
         let mut changes = Vec::new();
         
         for (name, link) in &mut robot.links {
-            if link.inertial.is_none() && (!link.visual.is_empty() || !link.collision.is_empty()) {
-                changes.push(format!("Would add default inertial properties to link: {}", name));
+            let primitive = self.primitive_geometry(link);
+            
+            match &mut link.inertial {
+                None if !link.visual.is_empty() || !link.collision.is_empty() => {
+                    let (origin, inertia) = match primitive {
+                        Some((origin, shape)) => (origin, self.compute_primitive_inertia(&shape, default_mass)),
+                        None => (None, self.compute_primitive_inertia(&GeometryShape::Box { size: [DEFAULT_INERTIA_SIDE; 3] }, default_mass)),
+                    };
+                    link.inertial = Some(Inertial { mass: default_mass, origin, inertia });
+                    changes.push(format!("Added default inertial properties to link: {} (mass {} kg)", name, default_mass));
+                }
+                Some(inertial) if inertial.inertia.is_none() => {
+                    if let Some((origin, shape)) = primitive {
+                        inertial.inertia = self.compute_primitive_inertia(&shape, inertial.mass);
+                        if inertial.origin.is_none() {
+                            inertial.origin = origin;
+                        }
+                        changes.push(format!("Computed inertia tensor of link {} from its geometry", name));
+                    }
+                }
+                _ => {}
             }
         }
         
         Ok(changes)
     }
 
-    fn sort_elements(&self, robot: &mut Robot) -> Result<Vec<String>, UrdfParseError> {
+    pub fn compute_primitive_inertia(&self, shape: &GeometryShape, mass: f64) -> Option<Inertia> {
+        let (ixx, iyy, izz) = match shape {
+            GeometryShape::Box { size: [x, y, z] } => (
+                mass * (y * y + z * z) / 12.0,
+                mass * (x * x + z * z) / 12.0,
+                mass * (x * x + y * y) / 12.0,
+            ),
+            GeometryShape::Cylinder { radius, length } => {
+                let transverse = mass * (3.0 * radius * radius + length * length) / 12.0;
+                (transverse, transverse, mass * radius * radius / 2.0)
+            }
+            GeometryShape::Sphere { radius } => {
+                let moment = 2.0 * mass * radius * radius / 5.0;
+                (moment, moment, moment)
+            }
+            GeometryShape::Mesh { .. } => return None,
+        };
+        
+        Some(Inertia { ixx, ixy: 0.0, ixz: 0.0, iyy, iyz: 0.0, izz })
+    }
+
+    fn primitive_geometry(&self, link: &Link) -> Option<(Option<Origin>, GeometryShape)> {
+        let collisions = link.collision.iter().map(|collision| (&collision.origin, &collision.geometry));
+        let visuals = link.visual.iter().map(|visual| (&visual.origin, &visual.geometry));
+        
+        collisions.chain(visuals)
+            .filter_map(|(origin, geometry)| geometry.as_ref().map(|geometry| (origin.clone(), geometry.shape.clone())))
+            .find(|(_, shape)| !matches!(shape, GeometryShape::Mesh { .. }))
+    }
+
+    pub fn sort_elements(&self, robot: &mut Robot, mode: SortMode) -> Result<Vec<String>, UrdfParseError> {
         let mut changes = Vec::new();
         
+        let (link_order, joint_order) = match mode {
+            SortMode::Alphabetical => {
+                let mut links: Vec<String> = robot.links.keys().cloned().collect();
+                let mut joints: Vec<String> = robot.joints.keys().cloned().collect();
+                links.sort();
+                joints.sort();
+                (links, joints)
+            }
+            SortMode::Topological => self.traversal_order(robot),
+        };
+        let description = match mode {
+            SortMode::Alphabetical => "alphabetically",
+            SortMode::Topological => "in kinematic tree order",
+        };
+        
         let original_links_order: Vec<String> = robot.links.keys().cloned().collect();
         let mut sorted_links = IndexMap::new();
-        let mut sorted_link_names = original_links_order.clone();
-        sorted_link_names.sort();
-        
-        for name in sorted_link_names {
-            if let Some(link) = robot.links.remove(&name) {
+        for name in link_order {
+            if let Some(link) = robot.links.swap_remove(&name) {
                 sorted_links.insert(name, link);
             }
         }
         robot.links = sorted_links;
         
         if original_links_order != robot.links.keys().cloned().collect::<Vec<_>>() {
-            changes.push("Sorted links alphabetically".to_string());
+            changes.push(format!("Sorted links {}", description));
         }
         
         let original_joints_order: Vec<String> = robot.joints.keys().cloned().collect();
         let mut sorted_joints = IndexMap::new();
-        let mut sorted_joint_names = original_joints_order.clone();
-        sorted_joint_names.sort();
-        
-        for name in sorted_joint_names {
-            if let Some(joint) = robot.joints.remove(&name) {
+        for name in joint_order {
+            if let Some(joint) = robot.joints.swap_remove(&name) {
                 sorted_joints.insert(name, joint);
             }
         }
         robot.joints = sorted_joints;
         
         if original_joints_order != robot.joints.keys().cloned().collect::<Vec<_>>() {
-            changes.push("Sorted joints alphabetically".to_string());
+            changes.push(format!("Sorted joints {}", description));
         }
         
         Ok(changes)
     }
 
-    fn try_auto_fix(&self, _doc: &mut UrdfDocument, issue: &UrdfIssue) -> Result<Option<String>, UrdfParseError> {
+    fn traversal_order(&self, robot: &Robot) -> (Vec<String>, Vec<String>) {
+        let child_links: HashSet<&str> = robot.joints.values().map(|joint| joint.child.as_str()).collect();
+        let mut queue: VecDeque<String> = robot.links.keys()
+            .filter(|name| !child_links.contains(name.as_str()))
+            .cloned()
+            .collect();
+        
+        let mut visited = HashSet::new();
+        let mut links = Vec::new();
+        let mut joints = Vec::new();
+        
+        while let Some(link_name) = queue.pop_front() {
+            if !visited.insert(link_name.clone()) {
+                continue;
+            }
+            if robot.links.contains_key(&link_name) {
+                links.push(link_name.clone());
+            }
+            for (joint_name, joint) in &robot.joints {
+                if joint.parent == link_name {
+                    joints.push(joint_name.clone());
+                    queue.push_back(joint.child.clone());
+                }
+            }
+        }
+        
+        for name in robot.links.keys() {
+            if !links.contains(name) {
+                links.push(name.clone());
+            }
+        }
+        for name in robot.joints.keys() {
+            if !joints.contains(name) {
+                joints.push(name.clone());
+            }
+        }
+        
+        (links, joints)
+    }
+
+    fn relabel_names(&self, order: &[String], kind: &str, pattern: &str) -> Result<HashMap<String, String>, UrdfParseError> {
+        let mut names = HashMap::new();
+        let mut taken = HashSet::new();
+        
+        for (index, old_name) in order.iter().enumerate() {
+            let new_name = pattern
+                .replace("{kind}", kind)
+                .replace("{index}", &index.to_string());
+            if !taken.insert(new_name.clone()) {
+                return Err(UrdfParseError::InvalidStructure(format!(
+                    "Relabel pattern '{}' gives more than one {} the name '{}'; include {{index}} in the pattern",
+                    pattern, kind, new_name
+                )));
+            }
+            names.insert(old_name.clone(), new_name);
+        }
+        
+        Ok(names)
+    }
+
+    fn rename_links(&self, robot: &mut Robot, renames: &HashMap<String, String>) {
+        let links = std::mem::take(&mut robot.links);
+        robot.links = links.into_iter()
+            .map(|(name, mut link)| {
+                link.name = renames.get(&name).cloned().unwrap_or(name);
+                (link.name.clone(), link)
+            })
+            .collect();
+        self.update_link_references(robot, renames);
+    }
+
+    fn rename_joints(&self, robot: &mut Robot, renames: &HashMap<String, String>) -> Result<(), UrdfParseError> {
+        let joints = std::mem::take(&mut robot.joints);
+        robot.joints = joints.into_iter()
+            .map(|(name, mut joint)| {
+                joint.name = renames.get(&name).cloned().unwrap_or(name);
+                (joint.name.clone(), joint)
+            })
+            .collect();
+        self.update_joint_references(robot, renames)
+    }
+
+    fn rename_materials(&self, robot: &mut Robot, renames: &HashMap<String, String>) {
+        let materials = std::mem::take(&mut robot.materials);
+        robot.materials = materials.into_iter()
+            .map(|(name, mut material)| {
+                material.name = renames.get(&name).cloned().unwrap_or(name);
+                (material.name.clone(), material)
+            })
+            .collect();
+        self.update_material_references(robot, renames);
+    }
+
+    fn update_link_references(&self, robot: &mut Robot, renames: &HashMap<String, String>) {
+        self.update_comment_anchors(robot, "link", renames);
+        self.update_comment_anchors(robot, "gazebo", renames);
+        for joint in robot.joints.values_mut() {
+            if let Some(new_name) = renames.get(&joint.parent) {
+                joint.parent = new_name.clone();
+            }
+            if let Some(new_name) = renames.get(&joint.child) {
+                joint.child = new_name.clone();
+            }
+        }
+        for gazebo in &mut robot.gazebo_elements {
+            if let Some(reference) = &mut gazebo.reference
+                && let Some(new_name) = renames.get(reference)
+            {
+                *reference = new_name.clone();
+            }
+        }
+    }
+
+    fn update_joint_references(&self, robot: &mut Robot, renames: &HashMap<String, String>) -> Result<(), UrdfParseError> {
+        self.update_comment_anchors(robot, "joint", renames);
+        for joint in robot.joints.values_mut() {
+            if let Some(mimic) = &mut joint.mimic
+                && let Some(new_name) = renames.get(&mimic.joint)
+            {
+                mimic.joint = new_name.clone();
+            }
+        }
+        // A <gazebo reference> names a link or a joint; links already had their turn in update_link_references.
+        for gazebo in &mut robot.gazebo_elements {
+            if let Some(reference) = &mut gazebo.reference
+                && !robot.links.contains_key(reference.as_str())
+                && let Some(new_name) = renames.get(reference)
+            {
+                *reference = new_name.clone();
+            }
+        }
+        for transmission in &mut robot.transmission_elements {
+            transmission.content = rename_joint_tags(&transmission.content, renames)?;
+        }
+        Ok(())
+    }
+
+    fn update_material_references(&self, robot: &mut Robot, renames: &HashMap<String, String>) {
+        self.update_comment_anchors(robot, "material", renames);
+        for link in robot.links.values_mut() {
+            for material_ref in link.visual.iter_mut().filter_map(|visual| visual.material.as_mut()) {
+                if let Some(new_name) = renames.get(&material_ref.name) {
+                    material_ref.name = new_name.clone();
+                }
+            }
+        }
+    }
+
+    fn update_comment_anchors(&self, robot: &mut Robot, kind: &str, renames: &HashMap<String, String>) {
+        for comment in &mut robot.comments {
+            if let CommentAnchor::Before { kind: anchor_kind, name } = &mut comment.anchor
+                && let Some(new_name) = renames.get(name).filter(|_| anchor_kind == kind)
+            {
+                *name = new_name.clone();
+            }
+        }
+    }
+
+    fn try_auto_fix(&self, _doc: &mut UrdfDocument, _issue: &UrdfIssue) -> Result<Option<String>, UrdfParseError> {
         Ok(None)
     }
 
@@ -367,96 +973,418 @@ impl UrdfModifier {
 
     fn regenerate_xml_with_formatting(&self, doc: &mut UrdfDocument, options: &FormatOptions) -> Result<(), UrdfParseError> {
         let mut buffer = Vec::new();
-        let mut writer = Writer::new_with_indent(Cursor::new(&mut buffer), options.indent.as_bytes(), options.indent.len());
-        
-        let mut robot_element = BytesStart::new("robot");
-        robot_element.push_attribute(("name", doc.robot.name.as_str()));
-        writer.write_event(Event::Start(robot_element.to_borrowed()))?;
+        let indent_char = options.indent.as_bytes().first().copied().unwrap_or(b' ');
+        let mut writer = Writer::new_with_indent(Cursor::new(&mut buffer), indent_char, options.indent.len());
+        let mut emitted = vec![!options.preserve_comments; doc.robot.comments.len()];
         
-        for material in doc.robot.materials.values() {
-            self.write_material(&mut writer, material, options)?;
+        if options.emit_xml_declaration {
+            // Keep the source's version and encoding; a document without a declaration gets a bare 1.0 one.
+            let declaration = doc.robot.declaration.as_ref();
+            writer.write_event(Event::Decl(BytesDecl::new(
+                declaration.map_or("1.0", |declaration| declaration.version.as_str()),
+                declaration.and_then(|declaration| declaration.encoding.as_deref()),
+                declaration.and_then(|declaration| declaration.standalone.as_deref()),
+            )))?;
         }
         
-        for link in doc.robot.links.values() {
-            self.write_link(&mut writer, link, options)?;
-        }
+        self.write_comments(&mut writer, &doc.robot, &mut emitted, |anchor| *anchor == CommentAnchor::Document)?;
         
-        for joint in doc.robot.joints.values() {
-            self.write_joint(&mut writer, joint, options)?;
+        let mut robot_element = BytesStart::new("robot");
+        robot_element.push_attribute(("name", doc.robot.name.as_str()));
+        for (key, value) in &doc.robot.attributes {
+            robot_element.push_attribute((key.as_str(), value.as_str()));
         }
+        self.emit(&mut writer, Event::Start(robot_element.borrow()), options)?;
         
-        for gazebo in &doc.robot.gazebo_elements {
-            self.write_gazebo(&mut writer, gazebo, options)?;
-        }
+        let unlisted = ROBOT_ELEMENT_ORDER.iter()
+            .copied()
+            .filter(|tag| !options.element_order.iter().any(|listed| listed == tag));
+        let order = options.element_order.iter().map(String::as_str).chain(unlisted);
         
-        for transmission in &doc.robot.transmission_elements {
-            self.write_transmission(&mut writer, transmission, options)?;
+        for tag in order {
+            match tag {
+                "material" => {
+                    for material in doc.robot.materials.values() {
+                        self.write_comments(&mut writer, &doc.robot, &mut emitted, |anchor| self.anchored_to(anchor, "material", &material.name))?;
+                        self.write_material(&mut writer, material, options)?;
+                    }
+                }
+                "link" => {
+                    for link in doc.robot.links.values() {
+                        self.write_comments(&mut writer, &doc.robot, &mut emitted, |anchor| self.anchored_to(anchor, "link", &link.name))?;
+                        self.write_link(&mut writer, link, options)?;
+                        
+                        if options.interleave_joints {
+                            for joint in doc.robot.joints.values().filter(|joint| joint.parent == link.name) {
+                                self.write_comments(&mut writer, &doc.robot, &mut emitted, |anchor| self.anchored_to(anchor, "joint", &joint.name))?;
+                                self.write_joint(&mut writer, joint, options)?;
+                            }
+                        }
+                    }
+                }
+                "joint" => {
+                    let interleaved = |parent: &str| options.interleave_joints && doc.robot.links.contains_key(parent);
+                    for joint in doc.robot.joints.values().filter(|joint| !interleaved(&joint.parent)) {
+                        self.write_comments(&mut writer, &doc.robot, &mut emitted, |anchor| self.anchored_to(anchor, "joint", &joint.name))?;
+                        self.write_joint(&mut writer, joint, options)?;
+                    }
+                }
+                "gazebo" => {
+                    for gazebo in &doc.robot.gazebo_elements {
+                        self.write_comments(&mut writer, &doc.robot, &mut emitted, |anchor| self.anchored_to(anchor, "gazebo", gazebo.reference.as_deref().unwrap_or_default()))?;
+                        self.write_gazebo(&mut writer, gazebo, options)?;
+                    }
+                }
+                "transmission" => {
+                    for transmission in &doc.robot.transmission_elements {
+                        self.write_comments(&mut writer, &doc.robot, &mut emitted, |anchor| self.anchored_to(anchor, "transmission", &transmission.name))?;
+                        self.write_transmission(&mut writer, transmission, options)?;
+                    }
+                }
+                _ => {}
+            }
         }
         
+        // Comments whose element was removed or renamed away end up before </robot> instead of being lost.
+        self.write_comments(&mut writer, &doc.robot, &mut emitted, |_| true)?;
         writer.write_event(Event::End(BytesStart::new("robot").to_end()))?;
         
-        doc.raw_xml = String::from_utf8(buffer)
+        let xml = String::from_utf8(buffer)
             .map_err(|e| UrdfParseError::InvalidStructure(format!("UTF-8 error: {}", e)))?;
         
+        doc.raw_xml = match options.max_line_length {
+            Some(max_line_length) => self.wrap_long_tags(&xml, max_line_length),
+            None => xml,
+        };
+        
         Ok(())
     }
 
-    fn write_material(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, material: &Material, _options: &FormatOptions) -> Result<(), UrdfParseError> {
+    fn write_comments(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, robot: &Robot, emitted: &mut [bool], anchored: impl Fn(&CommentAnchor) -> bool) -> Result<(), UrdfParseError> {
+        for (comment, emitted) in robot.comments.iter().zip(emitted.iter_mut()) {
+            if !*emitted && anchored(&comment.anchor) {
+                writer.write_event(Event::Comment(BytesText::from_escaped(comment.text.as_str())))?;
+                *emitted = true;
+            }
+        }
+        Ok(())
+    }
+
+    fn anchored_to(&self, anchor: &CommentAnchor, kind: &str, name: &str) -> bool {
+        matches!(anchor, CommentAnchor::Before { kind: anchor_kind, name: anchor_name } if anchor_kind == kind && anchor_name == name)
+    }
+
+    fn wrap_long_tags(&self, xml: &str, max_line_length: usize) -> String {
+        xml.lines()
+            .map(|line| {
+                if line.chars().count() <= max_line_length {
+                    return line.to_string();
+                }
+                self.wrap_tag(line).unwrap_or_else(|| line.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn wrap_tag(&self, line: &str) -> Option<String> {
+        let content = line.trim_start();
+        let indent = &line[..line.len() - content.len()];
+        let tag = content.strip_prefix('<')?;
+        if tag.starts_with(['/', '?', '!']) {
+            return None;
+        }
+        
+        let name_end = tag.find(char::is_whitespace)?;
+        let name = &tag[..name_end];
+        let mut rest = &tag[name_end..];
+        let mut attributes = Vec::new();
+        
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() || rest.starts_with(['/', '>']) {
+                break;
+            }
+            
+            let eq = rest.find('=')?;
+            if rest[..eq].contains(char::is_whitespace) {
+                return None;
+            }
+            let quote = rest[eq + 1..].chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let close = eq + 2 + rest[eq + 2..].find(quote)?;
+            attributes.push(&rest[..=close]);
+            rest = &rest[close + 1..];
+        }
+        
+        if attributes.len() < 2 {
+            return None;
+        }
+        
+        let separator = format!("\n{}{}", indent, " ".repeat(name.len() + 2));
+        Some(format!("{}<{} {}{}", indent, name, attributes.join(&separator), rest))
+    }
+
+    fn write_material(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, material: &Material, options: &FormatOptions) -> Result<(), UrdfParseError> {
         let mut element = BytesStart::new("material");
         element.push_attribute(("name", material.name.as_str()));
         
         if material.color.is_some() || material.texture.is_some() {
-            writer.write_event(Event::Start(element.to_borrowed()))?;
+            self.emit(writer, Event::Start(element.borrow()), options)?;
+            if let Some(color) = &material.color {
+                let mut color_element = BytesStart::new("color");
+                color_element.push_attribute(("rgba", self.format_values(&color.rgba, options).as_str()));
+                self.emit(writer, Event::Empty(color_element), options)?;
+            }
+            if let Some(texture) = &material.texture {
+                let mut texture_element = BytesStart::new("texture");
+                texture_element.push_attribute(("filename", texture.filename.as_str()));
+                self.emit(writer, Event::Empty(texture_element), options)?;
+            }
             writer.write_event(Event::End(element.to_end()))?;
         } else {
-            writer.write_event(Event::Empty(element.to_borrowed()))?;
+            self.emit(writer, Event::Empty(element.borrow()), options)?;
         }
         
         Ok(())
     }
 
-    fn write_link(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, link: &Link, _options: &FormatOptions) -> Result<(), UrdfParseError> {
+    fn write_link(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, link: &Link, options: &FormatOptions) -> Result<(), UrdfParseError> {
         let mut element = BytesStart::new("link");
         element.push_attribute(("name", link.name.as_str()));
+        for (key, value) in &link.attributes {
+            element.push_attribute((key.as_str(), value.as_str()));
+        }
         
         let has_content = link.inertial.is_some() || !link.visual.is_empty() || !link.collision.is_empty();
         
         if has_content {
-            writer.write_event(Event::Start(element.to_borrowed()))?;
+            self.emit(writer, Event::Start(element.borrow()), options)?;
+            
+            let unlisted = LINK_ELEMENT_ORDER.iter()
+                .copied()
+                .filter(|tag| !options.link_element_order.iter().any(|listed| listed == tag));
+            let order = options.link_element_order.iter().map(String::as_str).chain(unlisted);
+            
+            for tag in order {
+                match tag {
+                    "inertial" => {
+                        if let Some(inertial) = &link.inertial {
+                            self.write_inertial(writer, inertial, options)?;
+                        }
+                    }
+                    "visual" => {
+                        for visual in &link.visual {
+                            self.write_visual(writer, visual, options)?;
+                        }
+                    }
+                    "collision" => {
+                        for collision in &link.collision {
+                            self.write_collision(writer, collision, options)?;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            
             writer.write_event(Event::End(element.to_end()))?;
         } else {
-            writer.write_event(Event::Empty(element.to_borrowed()))?;
+            self.emit(writer, Event::Empty(element.borrow()), options)?;
+        }
+        
+        Ok(())
+    }
+
+    fn write_inertial(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, inertial: &Inertial, options: &FormatOptions) -> Result<(), UrdfParseError> {
+        let element = BytesStart::new("inertial");
+        self.emit(writer, Event::Start(element.borrow()), options)?;
+        
+        if let Some(origin) = &inertial.origin {
+            self.write_origin(writer, origin, options)?;
+        }
+        
+        let mut mass_element = BytesStart::new("mass");
+        mass_element.push_attribute(("value", self.format_float(inertial.mass, options).as_str()));
+        self.emit(writer, Event::Empty(mass_element), options)?;
+        
+        if let Some(inertia) = &inertial.inertia {
+            let mut inertia_element = BytesStart::new("inertia");
+            inertia_element.push_attribute(("ixx", self.format_float(inertia.ixx, options).as_str()));
+            inertia_element.push_attribute(("ixy", self.format_float(inertia.ixy, options).as_str()));
+            inertia_element.push_attribute(("ixz", self.format_float(inertia.ixz, options).as_str()));
+            inertia_element.push_attribute(("iyy", self.format_float(inertia.iyy, options).as_str()));
+            inertia_element.push_attribute(("iyz", self.format_float(inertia.iyz, options).as_str()));
+            inertia_element.push_attribute(("izz", self.format_float(inertia.izz, options).as_str()));
+            self.emit(writer, Event::Empty(inertia_element), options)?;
+        }
+        
+        writer.write_event(Event::End(element.to_end()))?;
+        Ok(())
+    }
+
+    fn write_visual(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, visual: &Visual, options: &FormatOptions) -> Result<(), UrdfParseError> {
+        let mut element = BytesStart::new("visual");
+        if let Some(name) = &visual.name {
+            element.push_attribute(("name", name.as_str()));
+        }
+        
+        if visual.origin.is_none() && visual.geometry.is_none() && visual.material.is_none() {
+            self.emit(writer, Event::Empty(element.borrow()), options)?;
+            return Ok(());
+        }
+        
+        self.emit(writer, Event::Start(element.borrow()), options)?;
+        if let Some(origin) = &visual.origin {
+            self.write_origin(writer, origin, options)?;
         }
+        if let Some(geometry) = &visual.geometry {
+            self.write_geometry(writer, geometry, options)?;
+        }
+        if let Some(material) = &visual.material {
+            let material = Material {
+                name: material.name.clone(),
+                color: material.color.clone(),
+                texture: material.texture.clone(),
+            };
+            self.write_material(writer, &material, options)?;
+        }
+        writer.write_event(Event::End(element.to_end()))?;
+        
+        Ok(())
+    }
+
+    fn write_collision(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, collision: &Collision, options: &FormatOptions) -> Result<(), UrdfParseError> {
+        let mut element = BytesStart::new("collision");
+        if let Some(name) = &collision.name {
+            element.push_attribute(("name", name.as_str()));
+        }
+        
+        if collision.origin.is_none() && collision.geometry.is_none() {
+            self.emit(writer, Event::Empty(element.borrow()), options)?;
+            return Ok(());
+        }
+        
+        self.emit(writer, Event::Start(element.borrow()), options)?;
+        if let Some(origin) = &collision.origin {
+            self.write_origin(writer, origin, options)?;
+        }
+        if let Some(geometry) = &collision.geometry {
+            self.write_geometry(writer, geometry, options)?;
+        }
+        writer.write_event(Event::End(element.to_end()))?;
+        
+        Ok(())
+    }
+
+    fn write_geometry(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, geometry: &Geometry, options: &FormatOptions) -> Result<(), UrdfParseError> {
+        let element = BytesStart::new("geometry");
+        self.emit(writer, Event::Start(element.borrow()), options)?;
         
+        let shape_element = match &geometry.shape {
+            GeometryShape::Box { size } => {
+                let mut shape_element = BytesStart::new("box");
+                shape_element.push_attribute(("size", self.format_values(size, options).as_str()));
+                shape_element
+            }
+            GeometryShape::Cylinder { radius, length } => {
+                let mut shape_element = BytesStart::new("cylinder");
+                shape_element.push_attribute(("radius", self.format_float(*radius, options).as_str()));
+                shape_element.push_attribute(("length", self.format_float(*length, options).as_str()));
+                shape_element
+            }
+            GeometryShape::Sphere { radius } => {
+                let mut shape_element = BytesStart::new("sphere");
+                shape_element.push_attribute(("radius", self.format_float(*radius, options).as_str()));
+                shape_element
+            }
+            GeometryShape::Mesh { filename, scale } => {
+                let mut shape_element = BytesStart::new("mesh");
+                shape_element.push_attribute(("filename", filename.as_str()));
+                if let Some(scale) = scale {
+                    shape_element.push_attribute(("scale", self.format_values(scale, options).as_str()));
+                }
+                shape_element
+            }
+        };
+        self.emit(writer, Event::Empty(shape_element), options)?;
+        
+        writer.write_event(Event::End(element.to_end()))?;
         Ok(())
     }
 
-    fn write_joint(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, joint: &Joint, _options: &FormatOptions) -> Result<(), UrdfParseError> {
+    fn write_origin(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, origin: &Origin, options: &FormatOptions) -> Result<(), UrdfParseError> {
+        let is_identity = origin.xyz == [0.0; 3] && origin.rpy == [0.0; 3];
+        if options.minify && is_identity {
+            return Ok(());
+        }
+        
+        let mut origin_element = BytesStart::new("origin");
+        if !options.minify || origin.xyz != [0.0; 3] {
+            origin_element.push_attribute(("xyz", self.format_values(&origin.xyz, options).as_str()));
+        }
+        if !options.minify || origin.rpy != [0.0; 3] {
+            origin_element.push_attribute(("rpy", self.format_values(&origin.rpy, options).as_str()));
+        }
+        self.emit(writer, Event::Empty(origin_element), options)?;
+        Ok(())
+    }
+
+    fn write_joint(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, joint: &Joint, options: &FormatOptions) -> Result<(), UrdfParseError> {
         let mut element = BytesStart::new("joint");
         element.push_attribute(("name", joint.name.as_str()));
         element.push_attribute(("type", joint.joint_type.as_str()));
+        for (key, value) in &joint.attributes {
+            element.push_attribute((key.as_str(), value.as_str()));
+        }
         
-        writer.write_event(Event::Start(element.to_borrowed()))?;
+        self.emit(writer, Event::Start(element.borrow()), options)?;
         
         let mut parent_element = BytesStart::new("parent");
         parent_element.push_attribute(("link", joint.parent.as_str()));
-        writer.write_event(Event::Empty(parent_element.to_borrowed()))?;
+        self.emit(writer, Event::Empty(parent_element.borrow()), options)?;
         
         let mut child_element = BytesStart::new("child");
         child_element.push_attribute(("link", joint.child.as_str()));
-        writer.write_event(Event::Empty(child_element.to_borrowed()))?;
+        self.emit(writer, Event::Empty(child_element.borrow()), options)?;
         
         if let Some(origin) = &joint.origin {
-            let mut origin_element = BytesStart::new("origin");
-            origin_element.push_attribute(("xyz", format!("{} {} {}", origin.xyz[0], origin.xyz[1], origin.xyz[2]).as_str()));
-            origin_element.push_attribute(("rpy", format!("{} {} {}", origin.rpy[0], origin.rpy[1], origin.rpy[2]).as_str()));
-            writer.write_event(Event::Empty(origin_element.to_borrowed()))?;
+            self.write_origin(writer, origin, options)?;
         }
         
-        if let Some(axis) = &joint.axis {
+        if let Some(axis) = joint.axis.as_ref().filter(|axis| !(options.minify && axis.xyz == DEFAULT_AXIS)) {
             let mut axis_element = BytesStart::new("axis");
-            axis_element.push_attribute(("xyz", format!("{} {} {}", axis.xyz[0], axis.xyz[1], axis.xyz[2]).as_str()));
-            writer.write_event(Event::Empty(axis_element.to_borrowed()))?;
+            axis_element.push_attribute(("xyz", self.format_values(&axis.xyz, options).as_str()));
+            self.emit(writer, Event::Empty(axis_element.borrow()), options)?;
+        }
+        
+        if let Some(limit) = &joint.limit {
+            let mut limit_element = BytesStart::new("limit");
+            for (name, value) in [("lower", limit.lower), ("upper", limit.upper), ("effort", limit.effort), ("velocity", limit.velocity)] {
+                if let Some(value) = value {
+                    limit_element.push_attribute((name, self.format_float(value, options).as_str()));
+                }
+            }
+            self.emit(writer, Event::Empty(limit_element), options)?;
+        }
+        
+        if let Some(dynamics) = &joint.dynamics {
+            let mut dynamics_element = BytesStart::new("dynamics");
+            for (name, value) in [("damping", dynamics.damping), ("friction", dynamics.friction)] {
+                if let Some(value) = value {
+                    dynamics_element.push_attribute((name, self.format_float(value, options).as_str()));
+                }
+            }
+            self.emit(writer, Event::Empty(dynamics_element), options)?;
+        }
+        
+        if let Some(mimic) = &joint.mimic {
+            let mut mimic_element = BytesStart::new("mimic");
+            mimic_element.push_attribute(("joint", mimic.joint.as_str()));
+            if let Some(multiplier) = mimic.multiplier {
+                mimic_element.push_attribute(("multiplier", self.format_float(multiplier, options).as_str()));
+            }
+            if let Some(offset) = mimic.offset.filter(|offset| !(options.minify && *offset == 0.0)) {
+                mimic_element.push_attribute(("offset", self.format_float(offset, options).as_str()));
+            }
+            self.emit(writer, Event::Empty(mimic_element), options)?;
         }
         
         writer.write_event(Event::End(element.to_end()))?;
@@ -464,59 +1392,100 @@ impl UrdfModifier {
         Ok(())
     }
 
-    fn write_gazebo(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, gazebo: &crate::utils::parser::GazeboElement, _options: &FormatOptions) -> Result<(), UrdfParseError> {
+    fn write_gazebo(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, gazebo: &crate::utils::parser::GazeboElement, options: &FormatOptions) -> Result<(), UrdfParseError> {
         let mut element = BytesStart::new("gazebo");
         if let Some(reference) = &gazebo.reference {
             element.push_attribute(("reference", reference.as_str()));
         }
-        writer.write_event(Event::Empty(element.to_borrowed()))?;
+        
+        if gazebo.content.is_empty() {
+            self.emit(writer, Event::Empty(element.borrow()), options)?;
+        } else {
+            self.emit(writer, Event::Start(element.borrow()), options)?;
+            self.write_raw_content(writer, &gazebo.content)?;
+            writer.write_event(Event::End(element.to_end()))?;
+        }
         Ok(())
     }
 
-    fn write_transmission(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, transmission: &crate::utils::parser::TransmissionElement, _options: &FormatOptions) -> Result<(), UrdfParseError> {
-        let mut element = BytesStart::new("transmission");
-        element.push_attribute(("name", transmission.name.as_str()));
-        writer.write_event(Event::Empty(element.to_borrowed()))?;
+    fn write_raw_content(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, content: &str) -> Result<(), UrdfParseError> {
+        let reader = &mut Reader::from_str(content);
+        
+        loop {
+            match reader.read_event()? {
+                Event::Eof => break,
+                // Whitespace between tags is layout the writer redoes; any other text, e.g. a plugin parameter, is kept as written.
+                Event::Text(text) if text.iter().all(u8::is_ascii_whitespace) => {}
+                event => writer.write_event(event)?,
+            }
+        }
         Ok(())
     }
 
-    fn find_duplicate_links(&self, robot: &Robot) -> Vec<String> {
-        let mut seen = HashSet::new();
-        let mut duplicates = Vec::new();
+    fn write_transmission(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, transmission: &crate::utils::parser::TransmissionElement, options: &FormatOptions) -> Result<(), UrdfParseError> {
+        let mut element = BytesStart::new("transmission");
+        element.push_attribute(("name", transmission.name.as_str()));
         
-        for name in robot.links.keys() {
-            if !seen.insert(name.clone()) {
-                duplicates.push(name.clone());
-            }
+        if transmission.content.is_empty() {
+            self.emit(writer, Event::Empty(element.borrow()), options)?;
+        } else {
+            self.emit(writer, Event::Start(element.borrow()), options)?;
+            self.write_raw_content(writer, &transmission.content)?;
+            writer.write_event(Event::End(element.to_end()))?;
         }
-        
-        duplicates
+        Ok(())
     }
 
-    fn find_duplicate_joints(&self, robot: &Robot) -> Vec<String> {
-        let mut seen = HashSet::new();
-        let mut duplicates = Vec::new();
-        
-        for name in robot.joints.keys() {
-            if !seen.insert(name.clone()) {
-                duplicates.push(name.clone());
+    fn emit(&self, writer: &mut Writer<Cursor<&mut Vec<u8>>>, event: Event<'_>, options: &FormatOptions) -> Result<(), UrdfParseError> {
+        match event {
+            Event::Start(element) => writer.write_event(Event::Start(self.order_attributes(&element, options)))?,
+            Event::Empty(element) if options.compact_empty_elements => writer.write_event(Event::Empty(self.order_attributes(&element, options)))?,
+            Event::Empty(element) => {
+                let element = self.order_attributes(&element, options);
+                writer.write_event(Event::Start(element.borrow()))?;
+                writer.write_event(Event::Text(BytesText::new("")))?;
+                writer.write_event(Event::End(element.to_end()))?;
             }
+            event => writer.write_event(event)?,
         }
-        
-        duplicates
+        Ok(())
     }
 
-    fn find_duplicate_materials(&self, robot: &Robot) -> Vec<String> {
-        let mut seen = HashSet::new();
-        let mut duplicates = Vec::new();
+    fn order_attributes(&self, element: &BytesStart<'_>, options: &FormatOptions) -> BytesStart<'static> {
+        let mut attributes: Vec<_> = element.attributes().flatten().collect();
+        attributes.sort_by_key(|attribute| {
+            options.attribute_order.iter()
+                .position(|key| key.as_bytes() == attribute.key.as_ref())
+                .unwrap_or(options.attribute_order.len())
+        });
         
-        for name in robot.materials.keys() {
-            if !seen.insert(name.clone()) {
-                duplicates.push(name.clone());
+        let mut ordered = BytesStart::new(String::from_utf8_lossy(element.name().as_ref()).into_owned());
+        ordered.extend_attributes(attributes);
+        ordered
+    }
+
+    fn format_values(&self, values: &[f64], options: &FormatOptions) -> String {
+        values.iter().map(|value| self.format_float(*value, options)).collect::<Vec<_>>().join(" ")
+    }
+
+    // Display already gives the shortest string that round-trips; with a precision, trailing zeros are dropped so 1.50 and 1.5 agree.
+    fn format_float(&self, value: f64, options: &FormatOptions) -> String {
+        let text = match options.float_precision {
+            Some(precision) if value.is_finite() => {
+                let fixed = format!("{:.*}", precision, value);
+                if fixed.contains('.') {
+                    fixed.trim_end_matches('0').trim_end_matches('.').to_string()
+                } else {
+                    fixed
+                }
             }
+            _ => value.to_string(),
+        };
+        if text == "-0" {
+            "0".to_string()
+        } else {
+            text
         }
-        
-        duplicates
     }
 
     fn is_valid_name(&self, name: &str) -> bool {
@@ -554,6 +1523,38 @@ impl UrdfModifier {
     }
 }
 
+fn rename_joint_tags(content: &str, renames: &HashMap<String, String>) -> Result<String, UrdfParseError> {
+    let mut reader = Reader::from_str(content);
+    let mut writer = Writer::new(Vec::new());
+    
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) if e.name().as_ref() == b"joint" => writer.write_event(Event::Start(rename_attribute(&e, renames)?))?,
+            Event::Empty(e) if e.name().as_ref() == b"joint" => writer.write_event(Event::Empty(rename_attribute(&e, renames)?))?,
+            Event::Eof => break,
+            event => writer.write_event(event)?,
+        }
+    }
+    
+    String::from_utf8(writer.into_inner())
+        .map_err(|e| UrdfParseError::InvalidStructure(format!("UTF-8 error: {}", e)))
+}
+
+fn rename_attribute(element: &BytesStart, renames: &HashMap<String, String>) -> Result<BytesStart<'static>, UrdfParseError> {
+    let mut renamed = BytesStart::new(String::from_utf8_lossy(element.name().as_ref()).to_string());
+    for attr in element.attributes() {
+        let attr = attr.map_err(quick_xml::Error::from)?;
+        let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+        let value = attr.unescape_value()?.to_string();
+        let value = match renames.get(&value) {
+            Some(new_name) if key == "name" => new_name.clone(),
+            _ => value,
+        };
+        renamed.push_attribute((key.as_str(), value.as_str()));
+    }
+    Ok(renamed)
+}
+
 pub fn clean_xml_whitespace(xml: &str) -> Result<String, UrdfParseError> {
     let lines: Vec<&str> = xml.lines().collect();
     let cleaned_lines: Vec<String> = lines
@@ -566,8 +1567,6 @@ pub fn clean_xml_whitespace(xml: &str) -> Result<String, UrdfParseError> {
 }
 
 pub fn validate_xml_structure(xml: &str) -> Result<(), UrdfParseError> {
-    use quick_xml::Reader;
-    
     let mut reader = Reader::from_str(xml);
     reader.config_mut().trim_text(true);
     let mut buf = Vec::new();
@@ -575,11 +1574,367 @@ pub fn validate_xml_structure(xml: &str) -> Result<(), UrdfParseError> {
     loop {
         match reader.read_event_into(&mut buf) {
             Ok(Event::Eof) => break,
-            Err(e) => return Err(UrdfParseError::XmlError(e)),
+            Err(e) => return Err(UrdfParseError::XmlError(e).at(xml, reader.error_position() as usize)),
             _ => {}
         }
         buf.clear();
     }
     
     Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parser::{Axis, UrdfParser};
+
+    const CHAIN: &str = r#"<robot name="r">
+  <link name="a"/>
+  <link name="b"/>
+  <link name="c"/>
+  <link name="d"/>
+  <joint name="ab" type="fixed"><parent link="a"/><child link="b"/></joint>
+  <joint name="bc" type="revolute"><parent link="b"/><child link="c"/><axis xyz="0 0 1"/><limit lower="-1" upper="1" effort="1" velocity="1"/></joint>
+  <joint name="cd" type="fixed"><parent link="c"/><child link="d"/></joint>
+  <gazebo reference="a"><material>Gazebo/Grey</material></gazebo>
+  <gazebo reference="bc"><provideFeedback>true</provideFeedback></gazebo>
+  <transmission name="bc_trans"><type>transmission_interface/SimpleTransmission</type><joint name="bc"><hardwareInterface>EffortJointInterface</hardwareInterface></joint></transmission>
+</robot>"#;
+
+    fn parse(xml: &str) -> UrdfDocument {
+        UrdfParser::parse_string(xml).unwrap()
+    }
+
+    fn link_names(doc: &UrdfDocument) -> Vec<&str> {
+        doc.robot.links.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn remove_element_keeps_order() {
+        let mut doc = parse(CHAIN);
+        assert!(UrdfModifier.remove_element(&mut doc, "link", "b").unwrap());
+        assert_eq!(link_names(&doc), ["a", "c", "d"]);
+    }
+
+    #[test]
+    fn rename_element_keeps_order_and_references() {
+        let mut doc = parse(CHAIN);
+        assert!(UrdfModifier.rename_element(&mut doc, "link", "a", "z").unwrap());
+        assert_eq!(link_names(&doc), ["z", "b", "c", "d"]);
+        assert_eq!(doc.robot.joints["ab"].parent, "z");
+        assert_eq!(doc.robot.gazebo_elements[0].reference.as_deref(), Some("z"));
+        
+        assert!(UrdfModifier.rename_element(&mut doc, "joint", "bc", "elbow").unwrap());
+        let joints: Vec<&str> = doc.robot.joints.keys().map(String::as_str).collect();
+        assert_eq!(joints, ["ab", "elbow", "cd"]);
+        assert_eq!(doc.robot.gazebo_elements[1].reference.as_deref(), Some("elbow"));
+        assert!(doc.robot.transmission_elements[0].content.contains(r#"<joint name="elbow">"#));
+        assert!(doc.raw_xml.contains(r#"<joint name="elbow">"#));
+        
+        assert!(!UrdfModifier.rename_element(&mut doc, "link", "missing", "x").unwrap());
+    }
+
+    #[test]
+    fn remove_orphaned_links_keeps_order() {
+        let mut doc = parse(r#"<robot name="r">
+  <link name="base"/>
+  <link name="stray"/>
+  <link name="arm"/>
+  <link name="tip"/>
+  <joint name="j1" type="fixed"><parent link="base"/><child link="arm"/></joint>
+  <joint name="j2" type="fixed"><parent link="arm"/><child link="tip"/></joint>
+</robot>"#);
+        let changes = UrdfModifier.remove_orphaned_links(&mut doc).unwrap();
+        assert_eq!(changes, ["Removed orphaned link: stray"]);
+        assert_eq!(link_names(&doc), ["base", "arm", "tip"]);
+    }
+
+    #[test]
+    fn remove_orphaned_links_keeps_single_link_root() {
+        let mut doc = parse(r#"<robot name="r"><link name="base"/></robot>"#);
+        assert!(UrdfModifier.remove_orphaned_links(&mut doc).unwrap().is_empty());
+        assert_eq!(link_names(&doc), ["base"]);
+    }
+
+    #[test]
+    fn material_passes_keep_order() {
+        let xml = r#"<robot name="r">
+  <material name="red"><color rgba="1 0 0 1"/></material>
+  <material name="unused"><color rgba="0 1 0 1"/></material>
+  <material name="crimson"><color rgba="1 0 0 1"/></material>
+  <material name="blue"><color rgba="0 0 1 1"/></material>
+  <link name="a"><visual><geometry><box size="1 1 1"/></geometry><material name="crimson"/></visual></link>
+  <link name="b"><visual><geometry><box size="1 1 1"/></geometry><material name="blue"/></visual></link>
+  <link name="c"><visual><geometry><box size="1 1 1"/></geometry><material name="red"/></visual></link>
+</robot>"#;
+        let materials = |doc: &UrdfDocument| doc.robot.materials.keys().cloned().collect::<Vec<_>>();
+        
+        let mut doc = parse(xml);
+        UrdfModifier.merge_identical_materials(&mut doc.robot).unwrap();
+        assert_eq!(materials(&doc), ["red", "unused", "blue"]);
+        assert_eq!(doc.robot.links["a"].visual[0].material.as_ref().unwrap().name, "red");
+        
+        UrdfModifier.remove_unused_materials(&mut doc.robot).unwrap();
+        assert_eq!(materials(&doc), ["red", "blue"]);
+    }
+
+    #[test]
+    fn prefix_names_keeps_order_and_references() {
+        let mut doc = parse(CHAIN);
+        UrdfModifier.prefix_names(&mut doc, "b_").unwrap();
+        assert_eq!(link_names(&doc), ["b_a", "b_b", "b_c", "b_d"]);
+        assert_eq!(doc.robot.joints["b_bc"].parent, "b_b");
+        assert_eq!(doc.robot.gazebo_elements[1].reference.as_deref(), Some("b_bc"));
+        assert!(doc.raw_xml.contains(r#"<joint name="b_bc">"#));
+        
+        let mut doc = parse(r#"<robot name="r"><link name="x"/><link name="p_x"/></robot>"#);
+        assert!(UrdfModifier.prefix_names(&mut doc, "p_").is_err());
+    }
+
+    fn formatted(xml: &str, options: &FormatOptions) -> String {
+        let mut doc = parse(xml);
+        UrdfModifier.format_document(&mut doc, options).unwrap();
+        doc.raw_xml
+    }
+
+    // The defaults a URDF reader applies to omitted elements, so a minified model can be compared with its source.
+    fn with_spec_defaults(mut robot: Robot) -> Robot {
+        let identity = || Some(Origin { xyz: [0.0; 3], rpy: [0.0; 3] });
+        for link in robot.links.values_mut() {
+            if let Some(inertial) = link.inertial.as_mut() {
+                inertial.origin = inertial.origin.take().or_else(identity);
+            }
+            for visual in &mut link.visual {
+                visual.origin = visual.origin.take().or_else(identity);
+            }
+            for collision in &mut link.collision {
+                collision.origin = collision.origin.take().or_else(identity);
+            }
+        }
+        for joint in robot.joints.values_mut() {
+            joint.origin = joint.origin.take().or_else(identity);
+            joint.axis = Some(Axis { xyz: joint.axis.as_ref().map_or(DEFAULT_AXIS, |axis| axis.xyz), defaulted: false });
+            if let Some(mimic) = joint.mimic.as_mut() {
+                mimic.multiplier = mimic.multiplier.or(Some(1.0));
+                mimic.offset = mimic.offset.or(Some(0.0));
+            }
+        }
+        robot
+    }
+
+    #[test]
+    fn minified_output_reparses_to_the_same_model() {
+        let xml = r#"<?xml version="1.0"?>
+<robot name="r">
+  <link name="a">
+    <inertial><origin xyz="0 0 0" rpy="0 0 0"/><mass value="1"/><inertia ixx="1" ixy="0" ixz="0" iyy="1" iyz="0" izz="1"/></inertial>
+    <visual><origin xyz="0 0 0.5" rpy="0 0 0"/><geometry><box size="1 1 1"/></geometry></visual>
+    <collision><origin xyz="0 0 0" rpy="0 0 0"/><geometry><sphere radius="0.5"/></geometry></collision>
+  </link>
+  <link name="b"/>
+  <link name="c"/>
+  <joint name="j" type="revolute">
+    <origin xyz="0 0 0" rpy="0 0 0"/>
+    <parent link="a"/>
+    <child link="b"/>
+    <axis xyz="1 0 0"/>
+    <limit lower="-1" upper="1" effort="1" velocity="1"/>
+  </joint>
+  <joint name="k" type="continuous">
+    <origin xyz="0 0.2 0" rpy="0 0 0"/>
+    <parent link="a"/>
+    <child link="c"/>
+    <mimic joint="j" multiplier="1" offset="0"/>
+  </joint>
+</robot>"#;
+        let minify = FormatOptions { minify: true, ..FormatOptions::default() };
+        let minified = formatted(xml, &minify);
+        assert!(!minified.contains("<axis"));
+        assert!(!minified.contains("offset="));
+        assert!(!minified.contains(r#"rpy="0 0 0""#));
+        assert_eq!(formatted(&minified, &minify), minified);
+        
+        let original = with_spec_defaults(parse(xml).robot);
+        assert_eq!(with_spec_defaults(parse(&minified).robot), original);
+        assert_eq!(with_spec_defaults(parse(&formatted(&minified, &FormatOptions::default())).robot), original);
+    }
+
+    #[test]
+    fn namespaced_attributes_survive_formatting() {
+        let xml = r#"<robot name="r" xmlns:sim="urn:sim">
+  <link name="base" sim:group="body"/>
+  <link name="arm"/>
+  <joint name="j" type="fixed" sim:feedback="true"><parent link="base"/><child link="arm"/></joint>
+</robot>"#;
+        let output = formatted(xml, &FormatOptions::default());
+        assert!(output.contains(r#"<robot name="r" xmlns:sim="urn:sim">"#));
+        assert!(output.contains(r#"<link name="base" sim:group="body"/>"#));
+        assert!(output.contains(r#"<joint name="j" type="fixed" sim:feedback="true">"#));
+        assert_eq!(formatted(&output, &FormatOptions::default()), output);
+    }
+
+    #[test]
+    fn relabel_follows_gazebo_and_transmission_references() {
+        let mut doc = parse(CHAIN);
+        UrdfModifier.relabel(&mut doc.robot, "{kind}_{index}").unwrap();
+        assert_eq!(link_names(&doc), ["link_0", "link_1", "link_2", "link_3"]);
+        assert_eq!(doc.robot.gazebo_elements[0].reference.as_deref(), Some("link_0"));
+        assert_eq!(doc.robot.gazebo_elements[1].reference.as_deref(), Some("joint_1"));
+        assert!(doc.robot.transmission_elements[0].content.contains(r#"<joint name="joint_1">"#));
+    }
+
+    #[test]
+    fn transmission_inner_xml_survives_formatting() {
+        let xml = r#"<robot name="r">
+  <link name="a"/>
+  <link name="b"/>
+  <joint name="j" type="continuous"><parent link="a"/><child link="b"/><axis xyz="0 0 1"/></joint>
+  <transmission name="t">
+    <type>transmission_interface/SimpleTransmission</type>
+    <joint name="j"><hardwareInterface>hardware_interface/EffortJointInterface</hardwareInterface></joint>
+    <actuator name="motor"><mechanicalReduction>50</mechanicalReduction></actuator>
+  </transmission>
+</robot>"#;
+        let doc = parse(xml);
+        let content = &doc.robot.transmission_elements[0].content;
+        assert!(content.contains("<type>transmission_interface/SimpleTransmission</type>"));
+        assert!(content.contains("<mechanicalReduction>50</mechanicalReduction>"));
+        
+        let output = formatted(xml, &FormatOptions::default());
+        assert_eq!(parse(&output).robot.transmission_elements, doc.robot.transmission_elements);
+        assert!(output.contains("<hardwareInterface>hardware_interface/EffortJointInterface</hardwareInterface>"));
+        assert_eq!(formatted(&output, &FormatOptions::default()), output);
+    }
+
+    #[test]
+    fn normalize_axes_rescales_non_unit_axes_only() {
+        let mut doc = parse(r#"<robot name="r">
+  <link name="a"/><link name="b"/><link name="c"/><link name="d"/>
+  <joint name="long" type="continuous"><parent link="a"/><child link="b"/><axis xyz="0 0 2"/></joint>
+  <joint name="unit" type="continuous"><parent link="a"/><child link="c"/><axis xyz="0 0.6 0.8"/></joint>
+  <joint name="zero" type="continuous"><parent link="a"/><child link="d"/><axis xyz="0 0 0"/></joint>
+</robot>"#);
+        let changes = UrdfModifier.normalize_axes(&mut doc.robot).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].contains("joint long"));
+        
+        let axis = |name: &str| doc.robot.joints[name].axis.as_ref().unwrap().xyz;
+        assert_eq!(axis("long"), [0.0, 0.0, 1.0]);
+        assert_eq!(axis("unit"), [0.0, 0.6, 0.8]);
+        assert_eq!(axis("zero"), [0.0, 0.0, 0.0]);        
+        let mut untouched = parse(r#"<robot name="r"><link name="a"/><link name="b"/><joint name="long" type="continuous"><parent link="a"/><child link="b"/><axis xyz="0 0 2"/></joint></robot>"#);
+        UrdfModifier.fix_document(&mut untouched, &FixOptions::default()).unwrap();
+        assert_eq!(untouched.robot.joints["long"].axis.as_ref().unwrap().xyz, [0.0, 0.0, 2.0]);
+    }
+
+    #[test]
+    fn add_missing_properties_uses_default_mass_for_links_with_geometry() {
+        let mut doc = parse(r#"<robot name="r">
+  <link name="empty"/>
+  <link name="boxed"><collision><origin xyz="0 0 0.5"/><geometry><box size="1 2 3"/></geometry></collision></link>
+  <link name="meshed"><visual><geometry><mesh filename="part.stl"/></geometry></visual></link>
+  <link name="weighed"><inertial><mass value="4"/></inertial><visual><geometry><sphere radius="0.5"/></geometry></visual></link>
+</robot>"#);
+        let changes = UrdfModifier.add_missing_properties(&mut doc.robot, 2.0).unwrap();
+        assert_eq!(changes.len(), 3);
+        assert!(doc.robot.links["empty"].inertial.is_none());
+        
+        let boxed = doc.robot.links["boxed"].inertial.as_ref().unwrap();
+        assert_eq!(boxed.mass, 2.0);
+        assert_eq!(boxed.origin.as_ref().unwrap().xyz, [0.0, 0.0, 0.5]);
+        let inertia = boxed.inertia.as_ref().unwrap();
+        assert!((inertia.ixx - 2.0 * 13.0 / 12.0).abs() < 1e-12);
+        assert!((inertia.iyy - 2.0 * 10.0 / 12.0).abs() < 1e-12);
+        assert!((inertia.izz - 2.0 * 5.0 / 12.0).abs() < 1e-12);
+        
+        let meshed = doc.robot.links["meshed"].inertial.as_ref().unwrap();
+        assert_eq!(meshed.mass, 2.0);
+        assert!(meshed.inertia.is_some());
+        
+        let weighed = doc.robot.links["weighed"].inertial.as_ref().unwrap();
+        assert_eq!(weighed.mass, 4.0);
+        assert!((weighed.inertia.as_ref().unwrap().ixx - 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn strip_fixed_joint_extras_only_touches_fixed_joints() {
+        let mut doc = parse(r#"<robot name="r">
+  <link name="a"/><link name="b"/><link name="c"/>
+  <joint name="mount" type="fixed"><parent link="a"/><child link="b"/><axis xyz="0 0 1"/><dynamics damping="0.1"/></joint>
+  <joint name="hinge" type="continuous"><parent link="a"/><child link="c"/><axis xyz="0 0 1"/><dynamics damping="0.1"/></joint>
+</robot>"#);
+        let changes = UrdfModifier.strip_fixed_joint_extras(&mut doc.robot).unwrap();
+        assert_eq!(changes, ["Removed <axis>, <dynamics> from fixed joint mount"]);
+        
+        let mount = &doc.robot.joints["mount"];
+        assert!(mount.axis.is_none() && mount.limit.is_none() && mount.dynamics.is_none());
+        let hinge = &doc.robot.joints["hinge"];
+        assert!(hinge.axis.is_some() && hinge.dynamics.is_some());
+        
+        assert!(UrdfModifier.strip_fixed_joint_extras(&mut doc.robot).unwrap().is_empty());
+    }
+
+    #[test]
+    fn formatting_is_idempotent() {
+        let xml = r#"<?xml version="1.0"?>
+<!-- generated -->
+<robot name="r">
+  <material name="grey"><color rgba="0.5 0.5 0.5 1"/></material>
+  <link name="a"><visual><origin xyz="-0 0.1 -0.0" rpy="0 -0.0 0"/><geometry><box size="1 1 1"/></geometry><material name="grey"/></visual></link>
+  <link name="b"/>
+  <joint name="j" type="revolute"><origin xyz="0.123456789 -0.0000001 1e-9" rpy="-0 0 3.14159265"/><parent link="a"/><child link="b"/><axis xyz="0 0 1"/><limit lower="-1.5" upper="1.50" effort="10" velocity="1"/></joint>
+  <gazebo reference="a"><material>Gazebo/Grey</material></gazebo>
+</robot>"#;
+        for options in [
+            FormatOptions::default(),
+            FormatOptions { float_precision: Some(3), ..FormatOptions::default() },
+            FormatOptions { float_precision: Some(0), ..FormatOptions::default() },
+            FormatOptions { minify: true, ..FormatOptions::default() },
+        ] {
+            let once = formatted(xml, &options);
+            assert!(!once.contains("-0 ") && !once.contains("-0\""), "{}", once);
+            assert_eq!(formatted(&once, &options).as_bytes(), once.as_bytes(), "{:?}", options.float_precision);
+        }
+        
+        let rounded = formatted(xml, &FormatOptions { float_precision: Some(3), ..FormatOptions::default() });
+        assert!(rounded.contains(r#"xyz="0.123 0 0" rpy="0 0 3.142""#), "{}", rounded);
+    }
+
+    #[test]
+    fn link_children_round_trip_through_formatting() {
+        let xml = r#"<robot name="r">
+  <material name="m"><color rgba="0.2 0.4 0.6 1"/></material>
+  <link name="body">
+    <inertial><origin xyz="0 0 0.1" rpy="0 0 0"/><mass value="2.5"/><inertia ixx="0.1" ixy="0" ixz="0" iyy="0.2" iyz="0" izz="0.3"/></inertial>
+    <visual name="shell"><origin xyz="0.1 0.2 0.3" rpy="0 0 1.5"/><geometry><box size="1 2 3"/></geometry><material name="m"/></visual>
+    <collision name="hull"><origin xyz="0 0 0.5" rpy="0.1 0 0"/><geometry><cylinder radius="0.4" length="1.2"/></geometry></collision>
+  </link>
+</robot>"#;
+        let original = parse(xml).robot.links["body"].clone();
+        assert_eq!(original.visual.len(), 1);
+        
+        let output = formatted(xml, &FormatOptions::default());
+        assert!(output.contains(r#"<box size="1 2 3"/>"#), "{}", output);
+        assert_eq!(parse(&output).robot.links["body"], original);
+    }
+
+    #[test]
+    fn plugin_parameters_keep_their_whitespace() {
+        let xml = r#"<robot name="r">
+  <link name="a"/>
+  <gazebo>
+    <plugin name="p" filename="libp.so">
+      <robotNamespace>  /arm  </robotNamespace>
+      <args>
+        -topic joint_states
+      </args>
+    </plugin>
+  </gazebo>
+</robot>"#;
+        let output = formatted(xml, &FormatOptions::default());
+        assert!(output.contains("<robotNamespace>  /arm  </robotNamespace>"), "{output}");
+        assert!(output.contains("<args>\n        -topic joint_states\n      </args>"), "{output}");
+        assert!(output.contains("\n    <plugin name=\"p\" filename=\"libp.so\">\n      <robotNamespace>"), "{output}");
+        assert_eq!(formatted(&output, &FormatOptions::default()), output);
+    }
 }
\ No newline at end of file
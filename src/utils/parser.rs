@@ -1,10 +1,11 @@
 use quick_xml::{Reader, Writer, events::Event, name::QName};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::io::{BufRead, BufReader, Cursor, Write};
+use std::borrow::Cow;
+use std::io::BufRead;
 use std::fs;
 use thiserror::Error;
 use indexmap::IndexMap;
+use crate::utils::xacro::{self, XacroError};
 
 #[derive(Error, Debug)]
 pub enum UrdfParseError {
@@ -16,36 +17,124 @@ pub enum UrdfParseError {
     InvalidStructure(String),
     #[error("Missing required attribute: {0}")]
     MissingAttribute(String),
+    #[error("This is a xacro document (found {0}); expand it first, e.g. `xacro robot.urdf.xacro > robot.urdf`")]
+    Xacro(String),
+    #[error("xacro expansion failed: {0}")]
+    XacroExpansion(#[from] XacroError),
+    #[error("{source} (line {line}, column {column})")]
+    Located {
+        line: usize,
+        column: usize,
+        source: Box<UrdfParseError>,
+    },
 }
 
+impl UrdfParseError {
+    pub fn at(self, xml_content: &str, offset: usize) -> Self {
+        match self {
+            located @ UrdfParseError::Located { .. } => located,
+            error => {
+                let (line, column) = line_col(xml_content, offset);
+                UrdfParseError::Located { line, column, source: Box::new(error) }
+            }
+        }
+    }
+}
+
+pub fn line_col(xml_content: &str, offset: usize) -> (usize, usize) {
+    let mut end = offset.min(xml_content.len());
+    while !xml_content.is_char_boundary(end) {
+        end -= 1;
+    }
+    let before = &xml_content[..end];
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+}
+
+pub const JOINT_TYPES: [&str; 6] = ["revolute", "continuous", "prismatic", "fixed", "floating", "planar"];
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UrdfDocument {
     pub robot: Robot,
+    #[serde(default)]
     pub raw_xml: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressions: Vec<Suppression>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Suppression {
+    pub element_name: Option<String>,
+    pub categories: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Robot {
     pub name: String,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub attributes: IndexMap<String, String>,
     pub links: IndexMap<String, Link>,
     pub joints: IndexMap<String, Joint>,
     pub materials: IndexMap<String, Material>,
     pub gazebo_elements: Vec<GazeboElement>,
     pub transmission_elements: Vec<TransmissionElement>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<DuplicateElement>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<XmlComment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub declaration: Option<XmlDeclaration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct XmlDeclaration {
+    pub version: String,
+    pub encoding: Option<String>,
+    pub standalone: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct XmlComment {
+    pub text: String,
+    pub anchor: CommentAnchor,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentAnchor {
+    Document,
+    Before { kind: String, name: String },
+    End,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateElement {
+    pub kind: String,
+    pub name: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Link {
     pub name: String,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub attributes: IndexMap<String, String>,
     pub inertial: Option<Inertial>,
     pub visual: Vec<Visual>,
     pub collision: Vec<Collision>,
+    pub child_order: Vec<String>,
+    // Only set by the lenient parser, which skips the <origin> instead of failing.
+    #[serde(skip)]
+    pub direct_origin: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Joint {
     pub name: String,
     pub joint_type: String,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub attributes: IndexMap<String, String>,
     pub parent: String,
     pub child: String,
     pub origin: Option<Origin>,
@@ -93,6 +182,8 @@ pub struct Origin {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Axis {
     pub xyz: [f64; 3],
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub defaulted: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -152,6 +243,10 @@ pub struct Texture {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MaterialRef {
     pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub texture: Option<Texture>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -175,16 +270,109 @@ impl UrdfParser {
     }
 
     pub fn parse_string(xml_content: &str) -> Result<UrdfDocument, UrdfParseError> {
+        Self::parse_with(xml_content, None)
+    }
+
+    pub fn parse_string_lenient(xml_content: &str) -> Result<(UrdfDocument, Vec<UrdfParseError>), UrdfParseError> {
+        let mut errors = Vec::new();
+        let doc = Self::parse_with(xml_content, Some(&mut errors))?;
+        Ok((doc, errors))
+    }
+
+    // Opt-in expansion of property-only xacro; None means the text is already plain URDF.
+    // Callers decide whether expanded text may be written back over the source.
+    pub fn expand_xacro(xml_content: &str) -> Result<Option<String>, UrdfParseError> {
+        if Self::find_xacro(xml_content).is_none() {
+            return Ok(None);
+        }
+        
+        xacro::expand_properties(xml_content).map(Some).map_err(|error| match error {
+            XacroError::Unsupported { construct, offset } => UrdfParseError::Xacro(construct).at(xml_content, offset),
+            error => UrdfParseError::XacroExpansion(error),
+        })
+    }
+
+    // Unlike parse_string, a streamed document has an empty raw_xml and no urdfix-disable
+    // suppressions, its errors carry no line numbers, and xacro is only detected on <robot>
+    // and its direct children (the elements xacro constructs are normally declared as).
+    pub fn parse_reader<R: BufRead>(source: R) -> Result<UrdfDocument, UrdfParseError> {
+        let mut reader = Reader::from_reader(source);
+        reader.config_mut().trim_text(true);
+        
+        let robot = Self::read_robot(&mut reader, None, None)?;
+        Ok(Self::document(robot, String::new(), Vec::new()))
+    }
+
+    fn parse_with(xml_content: &str, errors: Option<&mut Vec<UrdfParseError>>) -> Result<UrdfDocument, UrdfParseError> {
+        if xml_content.trim().is_empty() {
+            return Err(UrdfParseError::InvalidStructure("input is empty".to_string()));
+        }
+        
+        if let Some((offset, construct)) = Self::find_xacro(xml_content) {
+            return Err(UrdfParseError::Xacro(construct).at(xml_content, offset));
+        }
+        
         let mut reader = Reader::from_str(xml_content);
         reader.config_mut().trim_text(true);
+        
+        let robot = Self::read_robot(&mut reader, Some(xml_content), errors)?;
+        Ok(Self::document(robot, xml_content.to_string(), Self::parse_suppressions(xml_content)?))
+    }
 
+    fn read_robot<R: BufRead>(reader: &mut Reader<R>, xml_content: Option<&str>, mut errors: Option<&mut Vec<UrdfParseError>>) -> Result<Robot, UrdfParseError> {
+        // Streamed input has no text to map byte offsets back to, so its errors stay unlocated.
+        let locate = |error: UrdfParseError, offset: u64| match xml_content {
+            Some(xml_content) => error.at(xml_content, offset as usize),
+            None => error,
+        };
+        
         let mut buf = Vec::new();
         let mut robot = None;
+        let mut document_comments = Vec::new();
+        let mut declaration = None;
+        // With trim_text on, whitespace produces no events, so a blank stream goes straight to Eof.
+        let mut empty = true;
         
         loop {
-            match reader.read_event_into(&mut buf)? {
+            let event = reader.read_event_into(&mut buf)
+                .map_err(|e| locate(UrdfParseError::from(e), reader.error_position()))?;
+            if !matches!(event, Event::Eof) {
+                empty = false;
+            }
+            match event {
+                Event::Start(ref e) | Event::Empty(ref e) if e.name() == QName(b"robot") && robot.is_some() => {
+                    let error = UrdfParseError::InvalidStructure("multiple <robot> elements found; a URDF file must describe exactly one robot".to_string());
+                    return Err(locate(error, reader.buffer_position()));
+                }
                 Event::Start(ref e) if e.name() == QName(b"robot") => {
-                    robot = Some(Self::parse_robot(&mut reader, e)?);
+                    let parsed = Self::parse_robot(reader, e, xml_content.unwrap_or_default(), errors.as_deref_mut()).map_err(|error| {
+                        let offset = match error {
+                            UrdfParseError::XmlError(_) => reader.error_position(),
+                            _ => reader.buffer_position(),
+                        };
+                        locate(error, offset)
+                    })?;
+                    robot = Some(parsed);
+                }
+                Event::Empty(ref e) if e.name() == QName(b"robot") => {
+                    let offset = reader.buffer_position();
+                    let shell = Self::robot_shell(e, xml_content.unwrap_or_default(), &mut errors, offset as usize)
+                        .map_err(|error| locate(error, offset))?;
+                    robot = Some(shell);
+                }
+                Event::Decl(ref decl) if robot.is_none() => {
+                    let text = |value: Cow<[u8]>| String::from_utf8_lossy(&value).to_string();
+                    declaration = Some(XmlDeclaration {
+                        version: text(decl.version()?),
+                        encoding: decl.encoding().transpose()?.map(text),
+                        standalone: decl.standalone().transpose()?.map(text),
+                    });
+                }
+                Event::Comment(ref text) if robot.is_none() => {
+                    document_comments.push(XmlComment {
+                        text: String::from_utf8_lossy(text.as_ref()).to_string(),
+                        anchor: CommentAnchor::Document,
+                    });
                 }
                 Event::Eof => break,
                 _ => {}
@@ -192,74 +380,313 @@ impl UrdfParser {
             buf.clear();
         }
 
-        let robot = robot.ok_or_else(|| UrdfParseError::InvalidStructure("No robot element found".to_string()))?;
+        if empty {
+            return Err(UrdfParseError::InvalidStructure("input is empty".to_string()));
+        }
+        let mut robot = robot.ok_or_else(|| UrdfParseError::InvalidStructure("no <robot> element found; is this a URDF file?".to_string()))?;
+        robot.comments.splice(0..0, document_comments);
+        robot.declaration = declaration;
+        Ok(robot)
+    }
+
+    fn document(robot: Robot, raw_xml: String, suppressions: Vec<Suppression>) -> UrdfDocument {
+        let warnings = robot.duplicates.iter()
+            .map(|duplicate| format!("duplicate {} '{}' overwrote previous definition", duplicate.kind, duplicate.name))
+            .collect();
         
-        Ok(UrdfDocument {
+        UrdfDocument {
             robot,
-            raw_xml: xml_content.to_string(),
-        })
+            raw_xml,
+            warnings,
+            suppressions,
+        }
     }
 
-    fn parse_robot(reader: &mut Reader<&[u8]>, start_event: &quick_xml::events::BytesStart) -> Result<Robot, UrdfParseError> {
-        let name = Self::get_required_attribute(start_event, b"name")?;
+    // Only xacro elements and ${...} expressions count: an expanded file often still declares xmlns:xacro.
+    fn find_xacro(xml_content: &str) -> Option<(usize, String)> {
+        let mut reader = Reader::from_str(xml_content);
         
-        let mut robot = Robot {
-            name,
-            links: IndexMap::new(),
-            joints: IndexMap::new(),
-            materials: IndexMap::new(),
-            gazebo_elements: Vec::new(),
-            transmission_elements: Vec::new(),
-        };
+        loop {
+            let offset = reader.buffer_position() as usize;
+            match reader.read_event() {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                    if let Some(construct) = Self::xacro_construct(&e) {
+                        return Some((offset, construct));
+                    }
+                }
+                Ok(Event::Eof) | Err(_) => return None,
+                _ => {}
+            }
+        }
+    }
 
-        let mut buf = Vec::new();
+    fn xacro_construct(element: &quick_xml::events::BytesStart) -> Option<String> {
+        if element.name().as_ref().starts_with(b"xacro:") {
+            return Some(format!("<{}>", String::from_utf8_lossy(element.name().as_ref())));
+        }
+        element.attributes().flatten()
+            .map(|attr| String::from_utf8_lossy(&attr.value).to_string())
+            .find(|value| value.contains("${"))
+            .map(|value| format!("expression \"{}\"", value))
+    }
+
+    fn parse_suppressions(xml_content: &str) -> Result<Vec<Suppression>, UrdfParseError> {
+        let mut reader = Reader::from_str(xml_content);
+        let mut depth = 0usize;
+        let mut pending: Vec<Vec<String>> = Vec::new();
+        let mut same_line: Vec<(usize, Vec<String>)> = Vec::new();
+        let mut element_lines: Vec<(usize, String)> = Vec::new();
+        let mut suppressions = Vec::new();
+        // Lines are counted incrementally; calling line_col per event made large files quadratic.
+        let mut line = 1;
+        let mut counted = 0;
+        
         loop {
-            match reader.read_event_into(&mut buf)? {
-                Event::Start(ref e) => {
-                    match e.name().as_ref() {
-                        b"link" => {
-                            let link = Self::parse_link(reader, e)?;
-                            robot.links.insert(link.name.clone(), link);
-                        }
-                        b"joint" => {
-                            let joint = Self::parse_joint(reader, e)?;
-                            robot.joints.insert(joint.name.clone(), joint);
-                        }
-                        b"material" => {
-                            let material = Self::parse_material(reader, e)?;
-                            robot.materials.insert(material.name.clone(), material);
-                        }
-                        b"gazebo" => {
-                            let gazebo = Self::parse_gazebo(reader, e)?;
-                            robot.gazebo_elements.push(gazebo);
-                        }
-                        b"transmission" => {
-                            let transmission = Self::parse_transmission(reader, e)?;
-                            robot.transmission_elements.push(transmission);
+            let position = reader.buffer_position() as usize;
+            line += xml_content[counted..position].matches('\n').count();
+            counted = position;
+            let (element, is_start) = match reader.read_event()? {
+                Event::Start(e) => (Some(e), true),
+                Event::Empty(e) => (Some(e), false),
+                Event::End(_) => {
+                    depth = depth.saturating_sub(1);
+                    (None, false)
+                }
+                Event::Comment(text) => {
+                    let text = String::from_utf8_lossy(text.as_ref()).trim().to_string();
+                    if let Some(rest) = text.strip_prefix("urdfix-disable-line") {
+                        same_line.push((line, Self::suppressed_categories(rest)));
+                    } else if let Some(rest) = text.strip_prefix("urdfix-disable") {
+                        if depth == 0 {
+                            suppressions.push(Suppression { element_name: None, categories: Self::suppressed_categories(rest) });
+                        } else if depth == 1 {
+                            pending.push(Self::suppressed_categories(rest));
                         }
-                        _ => {
-                            Self::skip_element(reader)?;
+                    }
+                    (None, false)
+                }
+                Event::Eof => break,
+                _ => (None, false),
+            };
+            
+            if let Some(element) = element {
+                if depth == 1 {
+                    let name = Self::get_optional_attribute(&element, b"name")
+                        .or_else(|| Self::get_optional_attribute(&element, b"reference"));
+                    if let Some(name) = name {
+                        for categories in pending.drain(..) {
+                            suppressions.push(Suppression { element_name: Some(name.clone()), categories });
                         }
+                        element_lines.push((line, name));
                     }
                 }
+                if is_start {
+                    depth += 1;
+                }
+            }
+        }
+        
+        for (line, categories) in same_line {
+            if let Some((_, name)) = element_lines.iter().find(|(element_line, _)| *element_line == line) {
+                suppressions.push(Suppression { element_name: Some(name.clone()), categories });
+            }
+        }
+        
+        Ok(suppressions)
+    }
+
+    fn suppressed_categories(rest: &str) -> Vec<String> {
+        rest.split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|category| !category.is_empty())
+            .map(|category| category.to_string())
+            .collect()
+    }
+
+    fn parse_robot<R: BufRead>(reader: &mut Reader<R>, start_event: &quick_xml::events::BytesStart, xml_content: &str, mut errors: Option<&mut Vec<UrdfParseError>>) -> Result<Robot, UrdfParseError> {
+        let mut robot = Self::robot_shell(start_event, xml_content, &mut errors, reader.buffer_position() as usize)?;
+
+        let mut buf = Vec::new();
+        let mut pending_comments = Vec::new();
+        loop {
+            let before = reader.buffer_position() as usize;
+            let event = reader.read_event_into(&mut buf)?;
+            // String input was already checked in full; this is what catches xacro in streamed input.
+            if let Event::Start(ref e) | Event::Empty(ref e) = event
+                && let Some(construct) = Self::xacro_construct(e) {
+                return Err(UrdfParseError::Xacro(construct));
+            }
+            match event {
+                Event::Start(ref e) if errors.is_some() => {
+                    // Parse the element from its own slice so a bad element can be skipped without losing our place.
+                    reader.read_to_end_into(e.name(), &mut Vec::new())?;
+                    let end = reader.buffer_position() as usize;
+                    let result = Self::parse_isolated(xml_content, before, end, &mut robot, &mut pending_comments);
+                    Self::recover(result, &mut errors, xml_content, end)?;
+                }
+                Event::Start(ref e) => Self::parse_element(reader, e, &mut robot, &mut pending_comments, false)?,
+                Event::Empty(ref e) => {
+                    let result = Self::parse_empty_element(e, &mut robot, &mut pending_comments);
+                    Self::recover(result, &mut errors, xml_content, reader.buffer_position() as usize)?;
+                }
+                Event::Comment(ref text) => pending_comments.push(String::from_utf8_lossy(text.as_ref()).to_string()),
                 Event::End(ref e) if e.name() == QName(b"robot") => break,
                 Event::Eof => return Err(UrdfParseError::InvalidStructure("Unexpected end of file".to_string())),
                 _ => {}
             }
             buf.clear();
         }
+        
+        robot.comments.extend(pending_comments.into_iter().map(|text| XmlComment { text, anchor: CommentAnchor::End }));
 
         Ok(robot)
     }
 
-    fn parse_link(reader: &mut Reader<&[u8]>, start_event: &quick_xml::events::BytesStart) -> Result<Link, UrdfParseError> {
+    fn robot_shell(start_event: &quick_xml::events::BytesStart, xml_content: &str, errors: &mut Option<&mut Vec<UrdfParseError>>, offset: usize) -> Result<Robot, UrdfParseError> {
+        if let Some(construct) = Self::xacro_construct(start_event) {
+            return Err(UrdfParseError::Xacro(construct));
+        }
+        
+        let name = Self::recover(Self::get_required_attribute(start_event, b"name"), errors, xml_content, offset)?
+            .unwrap_or_default();
+        
+        Ok(Robot {
+            name,
+            attributes: Self::extra_attributes(start_event)?,
+            links: IndexMap::new(),
+            joints: IndexMap::new(),
+            materials: IndexMap::new(),
+            gazebo_elements: Vec::new(),
+            transmission_elements: Vec::new(),
+            duplicates: Vec::new(),
+            comments: Vec::new(),
+            declaration: None,
+        })
+    }
+
+    fn recover<T>(result: Result<T, UrdfParseError>, errors: &mut Option<&mut Vec<UrdfParseError>>, xml_content: &str, offset: usize) -> Result<Option<T>, UrdfParseError> {
+        match (result, errors) {
+            (Ok(value), _) => Ok(Some(value)),
+            (Err(error), Some(errors)) => {
+                errors.push(error.at(xml_content, offset));
+                Ok(None)
+            }
+            (Err(error), None) => Err(error),
+        }
+    }
+
+    fn parse_isolated(xml_content: &str, start: usize, end: usize, robot: &mut Robot, pending_comments: &mut Vec<String>) -> Result<(), UrdfParseError> {
+        let mut reader = Reader::from_str(&xml_content[start..end]);
+        reader.config_mut().trim_text(true);
+        
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => {
+                    return Self::parse_element(&mut reader, &e, robot, pending_comments, true)
+                        .map_err(|error| error.at(xml_content, start + reader.buffer_position() as usize));
+                }
+                Ok(Event::Eof) => return Ok(()),
+                Err(error) => return Err(UrdfParseError::from(error).at(xml_content, start + reader.error_position() as usize)),
+                _ => {}
+            }
+        }
+    }
+
+    fn parse_element<R: BufRead>(reader: &mut Reader<R>, e: &quick_xml::events::BytesStart, robot: &mut Robot, pending_comments: &mut Vec<String>, lenient: bool) -> Result<(), UrdfParseError> {
+        match e.name().as_ref() {
+            b"link" => {
+                let link = Self::parse_link(reader, e, lenient)?;
+                let name = link.name.clone();
+                Self::anchor_comments(robot, pending_comments, "link", &name);
+                if robot.links.insert(name.clone(), link).is_some() {
+                    robot.duplicates.push(DuplicateElement { kind: "link".to_string(), name });
+                }
+            }
+            b"joint" => {
+                let joint = Self::parse_joint(reader, e)?;
+                let name = joint.name.clone();
+                Self::anchor_comments(robot, pending_comments, "joint", &name);
+                if robot.joints.insert(name.clone(), joint).is_some() {
+                    robot.duplicates.push(DuplicateElement { kind: "joint".to_string(), name });
+                }
+            }
+            b"material" => {
+                let material = Self::parse_material(reader, e)?;
+                let name = material.name.clone();
+                Self::anchor_comments(robot, pending_comments, "material", &name);
+                if robot.materials.insert(name.clone(), material).is_some() {
+                    robot.duplicates.push(DuplicateElement { kind: "material".to_string(), name });
+                }
+            }
+            b"gazebo" => {
+                let gazebo = Self::parse_gazebo(reader, e)?;
+                Self::anchor_comments(robot, pending_comments, "gazebo", gazebo.reference.as_deref().unwrap_or_default());
+                robot.gazebo_elements.push(gazebo);
+            }
+            b"transmission" => {
+                let transmission = Self::parse_transmission(reader, e)?;
+                Self::anchor_comments(robot, pending_comments, "transmission", &transmission.name);
+                robot.transmission_elements.push(transmission);
+            }
+            _ => {
+                Self::skip_element(reader)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_empty_element(e: &quick_xml::events::BytesStart, robot: &mut Robot, pending_comments: &mut Vec<String>) -> Result<(), UrdfParseError> {
+        match e.name().as_ref() {
+            b"link" => {
+                let name = Self::get_required_attribute(e, b"name")?;
+                Self::anchor_comments(robot, pending_comments, "link", &name);
+                let previous = robot.links.insert(name.clone(), Link {
+                    name: name.clone(),
+                    attributes: Self::namespaced_attributes(e, &[b"name"])?,
+                    inertial: None,
+                    visual: Vec::new(),
+                    collision: Vec::new(),
+                    child_order: Vec::new(),
+                    direct_origin: false,
+                });
+                if previous.is_some() {
+                    robot.duplicates.push(DuplicateElement { kind: "link".to_string(), name });
+                }
+            }
+            b"material" => {
+                let name = Self::get_required_attribute(e, b"name")?;
+                Self::anchor_comments(robot, pending_comments, "material", &name);
+                let previous = robot.materials.insert(name.clone(), Material {
+                    name: name.clone(),
+                    color: None,
+                    texture: None,
+                });
+                if previous.is_some() {
+                    robot.duplicates.push(DuplicateElement { kind: "material".to_string(), name });
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn anchor_comments(robot: &mut Robot, pending: &mut Vec<String>, kind: &str, name: &str) {
+        robot.comments.extend(pending.drain(..).map(|text| XmlComment {
+            text,
+            anchor: CommentAnchor::Before { kind: kind.to_string(), name: name.to_string() },
+        }));
+    }
+
+    fn parse_link<R: BufRead>(reader: &mut Reader<R>, start_event: &quick_xml::events::BytesStart, lenient: bool) -> Result<Link, UrdfParseError> {
         let name = Self::get_required_attribute(start_event, b"name")?;
         
         let mut link = Link {
             name,
+            attributes: Self::namespaced_attributes(start_event, &[b"name"])?,
             inertial: None,
             visual: Vec::new(),
             collision: Vec::new(),
+            child_order: Vec::new(),
+            direct_origin: false,
         };
 
         let mut buf = Vec::new();
@@ -267,12 +694,28 @@ impl UrdfParser {
             match reader.read_event_into(&mut buf)? {
                 Event::Start(ref e) => {
                     match e.name().as_ref() {
-                        b"inertial" => link.inertial = Some(Self::parse_inertial(reader, e)?),
-                        b"visual" => link.visual.push(Self::parse_visual(reader, e)?),
-                        b"collision" => link.collision.push(Self::parse_collision(reader, e)?),
+                        b"inertial" => {
+                            link.inertial = Some(Self::parse_inertial(reader, e)?);
+                            link.child_order.push("inertial".to_string());
+                        }
+                        b"visual" => {
+                            link.visual.push(Self::parse_visual(reader, e)?);
+                            link.child_order.push("visual".to_string());
+                        }
+                        b"collision" => {
+                            link.collision.push(Self::parse_collision(reader, e)?);
+                            link.child_order.push("collision".to_string());
+                        }
+                        b"origin" if lenient => {
+                            link.direct_origin = true;
+                            Self::skip_element(reader)?;
+                        }
+                        b"origin" => return Err(Self::link_origin_error(&link.name)),
                         _ => Self::skip_element(reader)?,
                     }
                 }
+                Event::Empty(ref e) if e.name() == QName(b"origin") && lenient => link.direct_origin = true,
+                Event::Empty(ref e) if e.name() == QName(b"origin") => return Err(Self::link_origin_error(&link.name)),
                 Event::End(ref e) if e.name() == QName(b"link") => break,
                 Event::Eof => return Err(UrdfParseError::InvalidStructure("Unexpected end of file".to_string())),
                 _ => {}
@@ -283,13 +726,21 @@ impl UrdfParser {
         Ok(link)
     }
 
-    fn parse_joint(reader: &mut Reader<&[u8]>, start_event: &quick_xml::events::BytesStart) -> Result<Joint, UrdfParseError> {
+    fn link_origin_error(link_name: &str) -> UrdfParseError {
+        UrdfParseError::InvalidStructure(format!(
+            "Link '{}' has a direct <origin> child; URDF link poses come from the <origin> of the joint that connects them, not from the link (this is SDF syntax)",
+            link_name
+        ))
+    }
+
+    fn parse_joint<R: BufRead>(reader: &mut Reader<R>, start_event: &quick_xml::events::BytesStart) -> Result<Joint, UrdfParseError> {
         let name = Self::get_required_attribute(start_event, b"name")?;
         let joint_type = Self::get_required_attribute(start_event, b"type")?;
         
         let mut joint = Joint {
             name,
             joint_type,
+            attributes: Self::namespaced_attributes(start_event, &[b"name", b"type"])?,
             parent: String::new(),
             child: String::new(),
             origin: None,
@@ -324,31 +775,60 @@ impl UrdfParser {
         Ok(joint)
     }
 
+    fn extra_attributes(element: &quick_xml::events::BytesStart) -> Result<IndexMap<String, String>, UrdfParseError> {
+        let mut attributes = IndexMap::new();
+        for attr in element.attributes() {
+            let attr = attr.map_err(quick_xml::Error::from)?;
+            if attr.key.as_ref() != b"name" {
+                let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+                attributes.insert(key, attr.unescape_value()?.to_string());
+            }
+        }
+        Ok(attributes)
+    }
+
+    // Prefixed attributes such as xmlns:foo or foo:bar are kept for the writer, except one that stood in for a missing unprefixed `used` attribute.
+    fn namespaced_attributes(element: &quick_xml::events::BytesStart, used: &[&[u8]]) -> Result<IndexMap<String, String>, UrdfParseError> {
+        let mut attributes = IndexMap::new();
+        let keys: Vec<Vec<u8>> = element.attributes().flatten().map(|attr| attr.key.as_ref().to_vec()).collect();
+        for attr in element.attributes() {
+            let attr = attr.map_err(quick_xml::Error::from)?;
+            if !attr.key.as_ref().contains(&b':') {
+                continue;
+            }
+            let local_name = attr.key.local_name();
+            let stood_in = attr.key.as_namespace_binding().is_none()
+                && used.contains(&local_name.as_ref())
+                && !keys.iter().any(|key| key == local_name.as_ref());
+            if !stood_in {
+                let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+                attributes.insert(key, attr.unescape_value()?.to_string());
+            }
+        }
+        Ok(attributes)
+    }
+
     fn get_required_attribute(element: &quick_xml::events::BytesStart, attr_name: &[u8]) -> Result<String, UrdfParseError> {
-        element.attributes()
-            .find_map(|attr| {
-                attr.ok().and_then(|a| {
-                    if a.key.as_ref() == attr_name {
-                        Some(String::from_utf8_lossy(&a.value).to_string())
-                    } else {
-                        None
-                    }
-                })
-            })
+        Self::get_optional_attribute(element, attr_name)
             .ok_or_else(|| UrdfParseError::MissingAttribute(String::from_utf8_lossy(attr_name).to_string()))
     }
 
     fn get_optional_attribute(element: &quick_xml::events::BytesStart, attr_name: &[u8]) -> Option<String> {
-        element.attributes()
-            .find_map(|attr| {
-                attr.ok().and_then(|a| {
-                    if a.key.as_ref() == attr_name {
-                        Some(String::from_utf8_lossy(&a.value).to_string())
-                    } else {
-                        None
-                    }
-                })
-            })
+        let mut prefixed_match = None;
+        
+        for attr in element.attributes().flatten() {
+            if attr.key.as_ref() == attr_name {
+                return Some(String::from_utf8_lossy(&attr.value).to_string());
+            }
+            if prefixed_match.is_none()
+                && attr.key.as_namespace_binding().is_none()
+                && attr.key.local_name().as_ref() == attr_name
+            {
+                prefixed_match = Some(String::from_utf8_lossy(&attr.value).to_string());
+            }
+        }
+        
+        prefixed_match
     }
 
     fn parse_origin_from_attributes(element: &quick_xml::events::BytesStart) -> Result<Origin, UrdfParseError> {
@@ -362,24 +842,25 @@ impl UrdfParser {
     }
 
     fn parse_axis_from_attributes(element: &quick_xml::events::BytesStart) -> Result<Axis, UrdfParseError> {
-        let xyz_str = Self::get_optional_attribute(element, b"xyz").unwrap_or_else(|| "1 0 0".to_string());
-        let xyz = Self::parse_three_floats(&xyz_str)?;
-        Ok(Axis { xyz })
+        let xyz_str = Self::get_optional_attribute(element, b"xyz");
+        let defaulted = xyz_str.is_none();
+        let xyz = Self::parse_three_floats(xyz_str.as_deref().unwrap_or("1 0 0"))?;
+        Ok(Axis { xyz, defaulted })
     }
 
     fn parse_limit_from_attributes(element: &quick_xml::events::BytesStart) -> Result<Limit, UrdfParseError> {
         Ok(Limit {
-            lower: Self::get_optional_attribute(element, b"lower").and_then(|s| s.parse().ok()),
-            upper: Self::get_optional_attribute(element, b"upper").and_then(|s| s.parse().ok()),
-            effort: Self::get_optional_attribute(element, b"effort").and_then(|s| s.parse().ok()),
-            velocity: Self::get_optional_attribute(element, b"velocity").and_then(|s| s.parse().ok()),
+            lower: Self::get_optional_attribute(element, b"lower").and_then(|s| Self::parse_float(&s)),
+            upper: Self::get_optional_attribute(element, b"upper").and_then(|s| Self::parse_float(&s)),
+            effort: Self::get_optional_attribute(element, b"effort").and_then(|s| Self::parse_float(&s)),
+            velocity: Self::get_optional_attribute(element, b"velocity").and_then(|s| Self::parse_float(&s)),
         })
     }
 
     fn parse_dynamics_from_attributes(element: &quick_xml::events::BytesStart) -> Result<Dynamics, UrdfParseError> {
         Ok(Dynamics {
-            damping: Self::get_optional_attribute(element, b"damping").and_then(|s| s.parse().ok()),
-            friction: Self::get_optional_attribute(element, b"friction").and_then(|s| s.parse().ok()),
+            damping: Self::get_optional_attribute(element, b"damping").and_then(|s| Self::parse_float(&s)),
+            friction: Self::get_optional_attribute(element, b"friction").and_then(|s| Self::parse_float(&s)),
         })
     }
 
@@ -387,17 +868,23 @@ impl UrdfParser {
         let joint = Self::get_required_attribute(element, b"joint")?;
         Ok(Mimic {
             joint,
-            multiplier: Self::get_optional_attribute(element, b"multiplier").and_then(|s| s.parse().ok()),
-            offset: Self::get_optional_attribute(element, b"offset").and_then(|s| s.parse().ok()),
+            multiplier: Self::get_optional_attribute(element, b"multiplier").and_then(|s| Self::parse_float(&s)),
+            offset: Self::get_optional_attribute(element, b"offset").and_then(|s| Self::parse_float(&s)),
         })
     }
 
+    // f64::from_str already takes scientific notation and inf/infinity in any
+    // case; what it does not do is skip the padding found around attribute values.
+    pub fn parse_float(value: &str) -> Option<f64> {
+        value.trim().parse().ok()
+    }
+
     fn parse_three_floats(s: &str) -> Result<[f64; 3], UrdfParseError> {
-        let parts: Result<Vec<f64>, _> = s.split_whitespace()
-            .map(|x| x.parse::<f64>())
+        let parts: Option<Vec<f64>> = s.split_whitespace()
+            .map(Self::parse_float)
             .collect();
         
-        let parts = parts.map_err(|_| UrdfParseError::InvalidStructure(format!("Invalid float array: {}", s)))?;
+        let parts = parts.ok_or_else(|| UrdfParseError::InvalidStructure(format!("Invalid float array: {}", s)))?;
         
         if parts.len() != 3 {
             return Err(UrdfParseError::InvalidStructure(format!("Expected 3 values, got {}", parts.len())));
@@ -406,63 +893,291 @@ impl UrdfParser {
         Ok([parts[0], parts[1], parts[2]])
     }
 
-    fn parse_inertial(reader: &mut Reader<&[u8]>, _start_event: &quick_xml::events::BytesStart) -> Result<Inertial, UrdfParseError> {
-        Self::skip_element(reader)?;
-        Ok(Inertial {
-            mass: 1.0,
+    fn parse_inertial<R: BufRead>(reader: &mut Reader<R>, _start_event: &quick_xml::events::BytesStart) -> Result<Inertial, UrdfParseError> {
+        let mut inertial = Inertial {
+            mass: 0.0,
             origin: None,
             inertia: None,
+        };
+
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Empty(ref e) | Event::Start(ref e) => {
+                    match e.name().as_ref() {
+                        b"mass" => inertial.mass = Self::parse_required_float(e, b"value")?,
+                        b"origin" => inertial.origin = Some(Self::parse_origin_from_attributes(e)?),
+                        b"inertia" => inertial.inertia = Some(Self::parse_inertia_from_attributes(e)?),
+                        _ => {}
+                    }
+                }
+                Event::End(ref e) if e.name() == QName(b"inertial") => break,
+                Event::Eof => return Err(UrdfParseError::InvalidStructure("Unexpected end of file".to_string())),
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(inertial)
+    }
+
+    fn parse_inertia_from_attributes(element: &quick_xml::events::BytesStart) -> Result<Inertia, UrdfParseError> {
+        Ok(Inertia {
+            ixx: Self::parse_required_float(element, b"ixx")?,
+            ixy: Self::parse_required_float(element, b"ixy")?,
+            ixz: Self::parse_required_float(element, b"ixz")?,
+            iyy: Self::parse_required_float(element, b"iyy")?,
+            iyz: Self::parse_required_float(element, b"iyz")?,
+            izz: Self::parse_required_float(element, b"izz")?,
         })
     }
 
-    fn parse_visual(reader: &mut Reader<&[u8]>, start_event: &quick_xml::events::BytesStart) -> Result<Visual, UrdfParseError> {
-        Self::skip_element(reader)?;
-        Ok(Visual {
+    fn parse_required_float(element: &quick_xml::events::BytesStart, attr_name: &[u8]) -> Result<f64, UrdfParseError> {
+        let value = Self::get_required_attribute(element, attr_name)?;
+        Self::parse_float(&value).ok_or_else(|| UrdfParseError::InvalidStructure(format!(
+            "Invalid value for attribute '{}': {}",
+            String::from_utf8_lossy(attr_name),
+            value
+        )))
+    }
+
+    fn parse_visual<R: BufRead>(reader: &mut Reader<R>, start_event: &quick_xml::events::BytesStart) -> Result<Visual, UrdfParseError> {
+        let mut visual = Visual {
             name: Self::get_optional_attribute(start_event, b"name"),
             origin: None,
             geometry: None,
             material: None,
-        })
+        };
+
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Empty(ref e) => {
+                    match e.name().as_ref() {
+                        b"origin" => visual.origin = Some(Self::parse_origin_from_attributes(e)?),
+                        b"material" => visual.material = Some(MaterialRef {
+                            name: Self::get_required_attribute(e, b"name")?,
+                            color: None,
+                            texture: None,
+                        }),
+                        _ => {}
+                    }
+                }
+                Event::Start(ref e) => {
+                    match e.name().as_ref() {
+                        b"geometry" => visual.geometry = Self::parse_geometry(reader)?,
+                        b"origin" => {
+                            visual.origin = Some(Self::parse_origin_from_attributes(e)?);
+                            Self::skip_element(reader)?;
+                        }
+                        b"material" => {
+                            let material = Self::parse_material(reader, e)?;
+                            visual.material = Some(MaterialRef {
+                                name: material.name,
+                                color: material.color,
+                                texture: material.texture,
+                            });
+                        }
+                        _ => Self::skip_element(reader)?,
+                    }
+                }
+                Event::End(ref e) if e.name() == QName(b"visual") => break,
+                Event::Eof => return Err(UrdfParseError::InvalidStructure("Unexpected end of file".to_string())),
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(visual)
     }
 
-    fn parse_collision(reader: &mut Reader<&[u8]>, start_event: &quick_xml::events::BytesStart) -> Result<Collision, UrdfParseError> {
-        Self::skip_element(reader)?;
-        Ok(Collision {
+    fn parse_collision<R: BufRead>(reader: &mut Reader<R>, start_event: &quick_xml::events::BytesStart) -> Result<Collision, UrdfParseError> {
+        let mut collision = Collision {
             name: Self::get_optional_attribute(start_event, b"name"),
             origin: None,
             geometry: None,
-        })
+        };
+
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Empty(ref e) if e.name() == QName(b"origin") => {
+                    collision.origin = Some(Self::parse_origin_from_attributes(e)?);
+                }
+                Event::Start(ref e) => {
+                    match e.name().as_ref() {
+                        b"geometry" => collision.geometry = Self::parse_geometry(reader)?,
+                        b"origin" => {
+                            collision.origin = Some(Self::parse_origin_from_attributes(e)?);
+                            Self::skip_element(reader)?;
+                        }
+                        _ => Self::skip_element(reader)?,
+                    }
+                }
+                Event::End(ref e) if e.name() == QName(b"collision") => break,
+                Event::Eof => return Err(UrdfParseError::InvalidStructure("Unexpected end of file".to_string())),
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(collision)
     }
 
-    fn parse_material(reader: &mut Reader<&[u8]>, start_event: &quick_xml::events::BytesStart) -> Result<Material, UrdfParseError> {
-        let name = Self::get_required_attribute(start_event, b"name")?;
-        Self::skip_element(reader)?;
-        Ok(Material {
-            name,
+    fn parse_geometry<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<Geometry>, UrdfParseError> {
+        let mut geometry = None;
+
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Empty(ref e) => {
+                    if let Some(shape) = Self::parse_geometry_shape(e)? {
+                        geometry = Some(Geometry { shape });
+                    }
+                }
+                Event::Start(ref e) => {
+                    if let Some(shape) = Self::parse_geometry_shape(e)? {
+                        geometry = Some(Geometry { shape });
+                    }
+                    Self::skip_element(reader)?;
+                }
+                Event::End(ref e) if e.name() == QName(b"geometry") => break,
+                Event::Eof => return Err(UrdfParseError::InvalidStructure("Unexpected end of file".to_string())),
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(geometry)
+    }
+
+    fn parse_geometry_shape(element: &quick_xml::events::BytesStart) -> Result<Option<GeometryShape>, UrdfParseError> {
+        let shape = match element.name().as_ref() {
+            b"box" => GeometryShape::Box {
+                size: Self::parse_three_floats(&Self::get_required_attribute(element, b"size")?)?,
+            },
+            b"cylinder" => GeometryShape::Cylinder {
+                radius: Self::parse_required_float(element, b"radius")?,
+                length: Self::parse_required_float(element, b"length")?,
+            },
+            b"sphere" => GeometryShape::Sphere {
+                radius: Self::parse_required_float(element, b"radius")?,
+            },
+            b"mesh" => GeometryShape::Mesh {
+                filename: Self::get_required_attribute(element, b"filename")?,
+                scale: Self::get_optional_attribute(element, b"scale")
+                    .map(|scale| Self::parse_three_floats(&scale))
+                    .transpose()?,
+            },
+            _ => return Ok(None),
+        };
+
+        Ok(Some(shape))
+    }
+
+    fn parse_material<R: BufRead>(reader: &mut Reader<R>, start_event: &quick_xml::events::BytesStart) -> Result<Material, UrdfParseError> {
+        let mut material = Material {
+            name: Self::get_required_attribute(start_event, b"name")?,
             color: None,
             texture: None,
-        })
+        };
+
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Empty(ref e) | Event::Start(ref e) => {
+                    match e.name().as_ref() {
+                        b"color" => material.color = Some(Self::parse_color_from_attributes(e)?),
+                        b"texture" => material.texture = Some(Texture {
+                            filename: Self::get_required_attribute(e, b"filename")?,
+                        }),
+                        _ => {}
+                    }
+                }
+                Event::End(ref e) if e.name() == QName(b"material") => break,
+                Event::Eof => return Err(UrdfParseError::InvalidStructure("Unexpected end of file".to_string())),
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(material)
+    }
+
+    fn parse_color_from_attributes(element: &quick_xml::events::BytesStart) -> Result<Color, UrdfParseError> {
+        let rgba_str = Self::get_required_attribute(element, b"rgba")?;
+        let parts: Option<Vec<f64>> = rgba_str.split_whitespace()
+            .map(Self::parse_float)
+            .collect();
+        
+        let parts = parts.ok_or_else(|| UrdfParseError::InvalidStructure(format!("Invalid float array: {}", rgba_str)))?;
+        
+        if parts.len() != 4 {
+            return Err(UrdfParseError::InvalidStructure(format!("Expected 4 values, got {}", parts.len())));
+        }
+        
+        Ok(Color { rgba: [parts[0], parts[1], parts[2], parts[3]] })
     }
 
-    fn parse_gazebo(reader: &mut Reader<&[u8]>, start_event: &quick_xml::events::BytesStart) -> Result<GazeboElement, UrdfParseError> {
+    fn parse_gazebo<R: BufRead>(reader: &mut Reader<R>, start_event: &quick_xml::events::BytesStart) -> Result<GazeboElement, UrdfParseError> {
         let reference = Self::get_optional_attribute(start_event, b"reference");
-        Self::skip_element(reader)?;
+        let content = Self::capture_inner_xml(reader)?;
         Ok(GazeboElement {
             reference,
-            content: String::new(),
+            content,
         })
     }
 
-    fn parse_transmission(reader: &mut Reader<&[u8]>, start_event: &quick_xml::events::BytesStart) -> Result<TransmissionElement, UrdfParseError> {
+    fn parse_transmission<R: BufRead>(reader: &mut Reader<R>, start_event: &quick_xml::events::BytesStart) -> Result<TransmissionElement, UrdfParseError> {
         let name = Self::get_required_attribute(start_event, b"name")?;
-        Self::skip_element(reader)?;
+        let content = Self::capture_inner_xml(reader)?;
         Ok(TransmissionElement {
             name,
-            content: String::new(),
+            content,
         })
     }
 
-    fn skip_element(reader: &mut Reader<&[u8]>) -> Result<(), UrdfParseError> {
+    fn capture_inner_xml<R: BufRead>(reader: &mut Reader<R>) -> Result<String, UrdfParseError> {
+        // Plugin parameters can depend on their surrounding whitespace, so only whitespace-only text (layout) is dropped.
+        let trim = (reader.config().trim_text_start, reader.config().trim_text_end);
+        reader.config_mut().trim_text(false);
+        let captured = Self::capture_untrimmed(reader);
+        reader.config_mut().trim_text_start = trim.0;
+        reader.config_mut().trim_text_end = trim.1;
+        captured
+    }
+
+    fn capture_untrimmed<R: BufRead>(reader: &mut Reader<R>) -> Result<String, UrdfParseError> {
+        let mut writer = Writer::new(Vec::new());
+        let mut depth = 1;
+        let mut buf = Vec::new();
+        
+        loop {
+            let event = reader.read_event_into(&mut buf)?;
+            match event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                Event::Text(ref text) if text.iter().all(u8::is_ascii_whitespace) => {
+                    buf.clear();
+                    continue;
+                }
+                Event::Eof => return Err(UrdfParseError::InvalidStructure("Unexpected end of file".to_string())),
+                _ => {}
+            }
+            writer.write_event(event)?;
+            buf.clear();
+        }
+        
+        String::from_utf8(writer.into_inner())
+            .map_err(|e| UrdfParseError::InvalidStructure(format!("UTF-8 error: {}", e)))
+    }
+
+    fn skip_element<R: BufRead>(reader: &mut Reader<R>) -> Result<(), UrdfParseError> {
         let mut depth = 1;
         let mut buf = Vec::new();
         
@@ -481,24 +1196,170 @@ impl UrdfParser {
 }
 
 pub fn validate_urdf_structure(doc: &UrdfDocument) -> Vec<String> {
+    urdf_structure_errors(doc).into_iter().map(|(_, message)| message).collect()
+}
+
+pub fn urdf_structure_errors(doc: &UrdfDocument) -> Vec<(&'static str, String)> {
     let mut issues = Vec::new();
     
     if doc.robot.name.is_empty() {
-        issues.push("Robot name is empty".to_string());
+        issues.push(("naming/empty-robot-name", "Robot name is empty".to_string()));
     }
     
     if doc.robot.links.is_empty() {
-        issues.push("No links defined in robot".to_string());
+        issues.push(("validation/no-links", "No links defined in robot".to_string()));
     }
     
     for joint in doc.robot.joints.values() {
         if !doc.robot.links.contains_key(&joint.parent) {
-            issues.push(format!("Joint '{}' references non-existent parent link '{}'", joint.name, joint.parent));
+            issues.push(("validation/undefined-link", format!("Joint '{}' references non-existent parent link '{}'", joint.name, joint.parent)));
         }
         if !doc.robot.links.contains_key(&joint.child) {
-            issues.push(format!("Joint '{}' references non-existent child link '{}'", joint.name, joint.child));
+            issues.push(("validation/undefined-link", format!("Joint '{}' references non-existent child link '{}'", joint.name, joint.child)));
         }
     }
     
     issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMESPACED: &str = r#"<robot name="r" xmlns:xacro="http://www.ros.org/wiki/xacro" xmlns:sim="urn:sim">
+  <link sim:name="base" sim:group="body"/>
+  <link name="arm" sim:group="body"/>
+  <joint name="j" type="fixed" sim:feedback="true"><parent link="base"/><child link="arm"/></joint>
+</robot>"#;
+
+    #[test]
+    fn namespaced_attributes_are_matched_and_kept() {
+        let doc = UrdfParser::parse_string(NAMESPACED).unwrap();
+        assert_eq!(doc.robot.attributes["xmlns:xacro"], "http://www.ros.org/wiki/xacro");
+        
+        let base = &doc.robot.links["base"];
+        assert_eq!(base.attributes.keys().collect::<Vec<_>>(), ["sim:group"]);
+        assert_eq!(doc.robot.links["arm"].attributes["sim:group"], "body");
+        assert_eq!(doc.robot.joints["j"].attributes["sim:feedback"], "true");
+    }
+
+    #[test]
+    fn link_origin_is_an_error_unless_lenient() {
+        let xml = r#"<robot name="r">
+  <link name="a"><origin xyz="1 0 0"/><visual><geometry><box size="1 1 1"/></geometry></visual></link>
+  <link name="b"/>
+</robot>"#;
+        assert!(UrdfParser::parse_string(xml).unwrap_err().to_string().contains("direct <origin>"));
+        
+        let (doc, errors) = UrdfParser::parse_string_lenient(xml).unwrap();
+        assert!(errors.is_empty());
+        let link = &doc.robot.links["a"];
+        assert!(link.direct_origin);
+        assert_eq!(link.visual.len(), 1);
+        assert!(!doc.robot.links["b"].direct_origin);
+    }
+
+    #[test]
+    fn float_attributes_accept_exponents_and_infinities() {
+        assert_eq!(UrdfParser::parse_float("1e-6"), Some(1e-6));
+        assert_eq!(UrdfParser::parse_float(" 2.5E3 "), Some(2500.0));
+        assert_eq!(UrdfParser::parse_float("inf"), Some(f64::INFINITY));
+        assert_eq!(UrdfParser::parse_float("-INF"), Some(f64::NEG_INFINITY));
+        assert_eq!(UrdfParser::parse_float("+Infinity"), Some(f64::INFINITY));
+        assert_eq!(UrdfParser::parse_float("1,5"), None);
+        
+        let doc = UrdfParser::parse_string(r#"<robot name="r">
+  <link name="a"/><link name="b"/>
+  <joint name="slide" type="prismatic"><parent link="a"/><child link="b"/><origin xyz="1e-6 0 0"/><axis xyz="0 0 1"/><limit lower="-INF" upper=" inf" effort="1e2" velocity="1"/></joint>
+</robot>"#).unwrap();
+        let joint = &doc.robot.joints["slide"];
+        assert_eq!(joint.origin.as_ref().unwrap().xyz[0], 1e-6);
+        let limit = joint.limit.as_ref().unwrap();
+        assert_eq!(limit.lower, Some(f64::NEG_INFINITY));
+        assert_eq!(limit.upper, Some(f64::INFINITY));
+        assert_eq!(limit.effort, Some(100.0));
+    }
+
+    #[test]
+    fn streamed_input_rejects_xacro() {
+        let plain = UrdfParser::parse_reader(NAMESPACED.as_bytes()).unwrap();
+        assert_eq!(plain.robot.links.len(), 2);
+        assert!(plain.raw_xml.is_empty());
+        
+        let macro_doc = r#"<robot name="r" xmlns:xacro="http://www.ros.org/wiki/xacro">
+  <xacro:property name="width" value="0.2"/>
+  <link name="base"/>
+</robot>"#;
+        let error = UrdfParser::parse_reader(macro_doc.as_bytes()).unwrap_err();
+        assert!(error.to_string().contains("<xacro:property>"), "{}", error);
+        
+        let expression = r#"<robot name="r"><link name="${prefix}_base"/></robot>"#;
+        let error = UrdfParser::parse_reader(expression.as_bytes()).unwrap_err();
+        assert!(error.to_string().contains("${prefix}_base"), "{}", error);
+    }
+
+    #[test]
+    fn disable_line_comments_attach_to_the_element_on_their_line() {
+        let doc = UrdfParser::parse_string(r#"<robot name="r">
+  <!-- urdfix-disable physics -->
+  <link name="a"/>
+  <link name="b"/> <!-- urdfix-disable-line naming -->
+  <link name="c"/>
+  <!-- urdfix-disable-line validation -->
+</robot>"#).unwrap();
+        let suppressions: Vec<_> = doc.suppressions.iter()
+            .map(|suppression| (suppression.element_name.as_deref(), suppression.categories.join(",")))
+            .collect();
+        assert_eq!(suppressions, [(Some("a"), "physics".to_string()), (Some("b"), "naming".to_string())]);
+    }
+
+    #[test]
+    fn a_second_robot_is_rejected_even_after_an_empty_one() {
+        let empty = UrdfParser::parse_string(r#"<robot name="empty"/>"#).unwrap();
+        assert_eq!(empty.robot.name, "empty");
+        assert!(empty.robot.links.is_empty());
+        
+        for xml in [
+            r#"<robot name="a"><link name="x"/></robot><robot name="b"><link name="y"/></robot>"#,
+            r#"<robot name="a"/><robot name="b"><link name="y"/></robot>"#,
+            r#"<robot name="a"><link name="x"/></robot><robot name="b"/>"#,
+        ] {
+            let error = UrdfParser::parse_string(xml).unwrap_err().to_string();
+            assert!(error.contains("multiple <robot> elements"), "{}", error);
+            assert!(UrdfParser::parse_reader(xml.as_bytes()).is_err());
+        }
+    }
+
+    #[test]
+    fn xacro_is_an_error_unless_expanded() {
+        let xml = r#"<robot name="r" xmlns:xacro="http://www.ros.org/wiki/xacro">
+  <xacro:property name="width" value="0.4"/>
+  <link name="base"><visual><geometry><box size="${width} ${width/2} 1"/></geometry></visual></link>
+</robot>"#;
+        let error = UrdfParser::parse_string(xml).unwrap_err().to_string();
+        assert!(error.contains("<xacro:property>") && error.contains("line 2"), "{}", error);
+        
+        let expanded = UrdfParser::expand_xacro(xml).unwrap().unwrap();
+        let doc = UrdfParser::parse_string(&expanded).unwrap();
+        assert!(doc.raw_xml.contains(r#"size="0.4 0.2 1""#));
+        assert_eq!(UrdfParser::expand_xacro(&expanded).unwrap(), None);
+        
+        let with_macro = xml.replace("<xacro:property name=\"width\" value=\"0.4\"/>", "<xacro:macro name=\"m\"/>");
+        let error = UrdfParser::expand_xacro(&with_macro).unwrap_err().to_string();
+        assert!(error.contains("<xacro:macro>") && error.contains("line 2"), "{}", error);
+    }
+
+    #[test]
+    fn empty_and_non_xml_input_get_specific_errors() {
+        for input in ["", " \n\t"] {
+            let error = UrdfParser::parse_string(input).unwrap_err();
+            assert!(matches!(&error, UrdfParseError::InvalidStructure(message) if message == "input is empty"), "{:?}", error);
+            let error = UrdfParser::parse_reader(input.as_bytes()).unwrap_err();
+            assert!(matches!(&error, UrdfParseError::InvalidStructure(message) if message == "input is empty"), "{:?}", error);
+        }
+        
+        for error in [UrdfParser::parse_string("hello world").unwrap_err(), UrdfParser::parse_reader("hello world".as_bytes()).unwrap_err()] {
+            assert!(error.to_string().contains("no <robot> element found"), "{}", error);
+        }
+    }
 }
\ No newline at end of file
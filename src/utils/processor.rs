@@ -1,21 +1,45 @@
-use crate::utils::parser::{UrdfDocument, Robot, Link, Joint, Material, UrdfParseError};
-use std::collections::{HashMap, HashSet};
+use crate::utils::resolver::MeshResolver;
+use crate::utils::transform::{IDENTITY, compose, origin_pose};
+use crate::utils::parser::{UrdfDocument, Robot, Link, Joint, Material, GeometryShape, Origin, Suppression, JOINT_TYPES, urdf_structure_errors};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::fmt;
 use indexmap::IndexMap;
 
 pub struct UrdfProcessor;
 
-#[derive(Debug, Clone)]
+pub const LINK_ELEMENT_ORDER: [&str; 3] = ["inertial", "visual", "collision"];
+
+pub const AXIS_NORM_TOLERANCE: f64 = 1e-6;
+
+pub fn joint_dof(joint_type: &str) -> usize {
+    match joint_type {
+        "revolute" | "continuous" | "prismatic" => 1,
+        "planar" => 3,
+        "floating" => 6,
+        _ => 0,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct UrdfStats {
     pub total_links: usize,
     pub total_joints: usize,
     pub total_materials: usize,
-    pub joint_types: HashMap<String, usize>,
+    pub joint_types: BTreeMap<String, usize>,
+    pub total_dof: usize,
+    pub dof_by_type: BTreeMap<String, usize>,
     pub link_properties: LinkProperties,
     pub tree_depth: usize,
     pub kinematic_chains: Vec<KinematicChain>,
+    pub connected_components: Vec<Vec<String>>,
+    pub total_mass: f64,
+    pub links_without_inertial: Vec<String>,
+    pub center_of_mass: Option<[f64; 3]>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct LinkProperties {
     pub with_visual: usize,
     pub with_collision: usize,
@@ -23,7 +47,7 @@ pub struct LinkProperties {
     pub empty_links: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct KinematicChain {
     pub name: String,
     pub links: Vec<String>,
@@ -32,22 +56,65 @@ pub struct KinematicChain {
 }
 
 #[derive(Debug, Clone)]
+pub struct LinkMass {
+    pub link: String,
+    pub mass: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct LinkGeometryCount {
+    pub link: String,
+    pub visuals: usize,
+    pub collisions: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ElementDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<(String, Vec<String>)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UrdfDiff {
+    pub robot_name: Option<(String, String)>,
+    pub links: ElementDiff,
+    pub joints: ElementDiff,
+    pub materials: ElementDiff,
+}
+
+impl ElementDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl UrdfDiff {
+    pub fn is_empty(&self) -> bool {
+        self.robot_name.is_none() && self.links.is_empty() && self.joints.is_empty() && self.materials.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct UrdfIssue {
     pub severity: IssueSeverity,
     pub category: IssueCategory,
+    pub rule_id: &'static str,
     pub message: String,
     pub element_name: Option<String>,
     pub suggestion: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum IssueSeverity {
     Error,
     Warning,
     Info,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum IssueCategory {
     Structure,
     Naming,
@@ -57,49 +124,239 @@ pub enum IssueCategory {
     Style,
 }
 
+impl fmt::Display for IssueSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            IssueSeverity::Error => "error",
+            IssueSeverity::Warning => "warning",
+            IssueSeverity::Info => "info",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl fmt::Display for IssueCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            IssueCategory::Structure => "structure",
+            IssueCategory::Naming => "naming",
+            IssueCategory::Physics => "physics",
+            IssueCategory::Geometry => "geometry",
+            IssueCategory::Validation => "validation",
+            IssueCategory::Style => "style",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LintOptions {
+    pub collision_mesh_severity: IssueSeverity,
+    pub detect_swapped_origins: bool,
+    pub check_link_element_order: bool,
+    pub mesh_resolver: Option<MeshResolver>,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self {
+            collision_mesh_severity: IssueSeverity::Warning,
+            detect_swapped_origins: false,
+            check_link_element_order: false,
+            mesh_resolver: None,
+        }
+    }
+}
+
 impl UrdfProcessor {
     pub fn analyze(&self, doc: &UrdfDocument) -> UrdfStats {
         let robot = &doc.robot;
         
         let joint_types = self.count_joint_types(robot);
+        let dof_by_type: BTreeMap<String, usize> = joint_types.iter()
+            .map(|(joint_type, count)| (joint_type.clone(), joint_dof(joint_type) * count))
+            .collect();
         let link_properties = self.analyze_link_properties(robot);
         let tree_depth = self.calculate_tree_depth(robot);
         let kinematic_chains = self.find_kinematic_chains(robot);
+        let total_mass = self.link_masses(doc).iter().map(|link| link.mass).sum();
+        let links_without_inertial = robot.links.values()
+            .filter(|link| link.inertial.is_none())
+            .map(|link| link.name.clone())
+            .collect();
         
         UrdfStats {
             total_links: robot.links.len(),
             total_joints: robot.joints.len(),
             total_materials: robot.materials.len(),
             joint_types,
+            total_dof: dof_by_type.values().sum(),
+            dof_by_type,
             link_properties,
             tree_depth,
             kinematic_chains,
+            connected_components: self.find_connected_components(doc),
+            total_mass,
+            links_without_inertial,
+            center_of_mass: self.center_of_mass(doc),
         }
     }
 
+    fn check_link_origins(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
+        doc.robot.links.iter()
+            .filter(|(_, link)| link.direct_origin)
+            .map(|(name, _)| UrdfIssue {
+                severity: IssueSeverity::Warning,
+                category: IssueCategory::Validation,
+                rule_id: "validation/link-origin",
+                message: format!("Link '{}' has a direct <origin> child, which URDF ignores; link poses come from the <origin> of the joint that connects them (this is SDF syntax)", name),
+                element_name: Some(name.clone()),
+                suggestion: Some("Move the pose to the parent joint's <origin>, or to the <visual>, <collision> or <inertial> origins".to_string()),
+            })
+            .collect()
+    }
+
     pub fn lint(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
+        self.lint_with_options(doc, &LintOptions::default())
+    }
+
+    pub fn lint_with_options(&self, doc: &UrdfDocument, options: &LintOptions) -> Vec<UrdfIssue> {
         let mut issues = Vec::new();
         
         issues.extend(self.check_naming_conventions(doc));
+        issues.extend(self.check_document_structure(doc));
         issues.extend(self.check_structural_issues(doc));
+        issues.extend(self.check_duplicate_joint_pairs(doc));
         issues.extend(self.check_physics_properties(doc));
+        issues.extend(self.check_inertia_tensors(doc));
         issues.extend(self.check_duplicate_elements(doc));
         issues.extend(self.check_unused_materials(doc));
+        issues.extend(self.check_joint_types(doc));
         issues.extend(self.check_joint_limits(doc));
+        issues.extend(self.check_fixed_joint_extras(doc));
+        issues.extend(self.check_link_origins(doc));
+        issues.extend(self.check_joint_axes(doc));
+        issues.extend(self.check_degree_rpy(doc));
+        issues.extend(self.check_mimic_references(doc));
+        issues.extend(self.check_collision_meshes(doc, options));
+        
+        if options.detect_swapped_origins {
+            issues.extend(self.check_swapped_origins(doc));
+        }
+        
+        if options.check_link_element_order {
+            issues.extend(self.check_link_element_order(doc));
+        }
+        
+        if let Some(resolver) = &options.mesh_resolver {
+            issues.extend(self.check_mesh_files(doc, resolver));
+        }
         
         issues
     }
 
-    pub fn find_duplicates(&self, doc: &UrdfDocument) -> HashMap<String, Vec<String>> {
-        let mut duplicates = HashMap::new();
+    pub fn apply_suppressions(&self, doc: &UrdfDocument, issues: Vec<UrdfIssue>) -> (Vec<UrdfIssue>, usize) {
+        let (suppressed, kept): (Vec<UrdfIssue>, Vec<UrdfIssue>) = issues.into_iter()
+            .partition(|issue| doc.suppressions.iter().any(|suppression| Self::suppresses(suppression, issue)));
+        (kept, suppressed.len())
+    }
+
+    fn suppresses(suppression: &Suppression, issue: &UrdfIssue) -> bool {
+        let element_matches = match &suppression.element_name {
+            Some(name) => issue.element_name.as_ref() == Some(name),
+            None => true,
+        };
+        let category = issue.category.to_string();
+        element_matches && (suppression.categories.is_empty() || suppression.categories.iter().any(|c| *c == category || c == issue.rule_id))
+    }
+
+    pub fn diff(&self, old: &UrdfDocument, new: &UrdfDocument) -> UrdfDiff {
+        let robot_name = if old.robot.name != new.robot.name {
+            Some((old.robot.name.clone(), new.robot.name.clone()))
+        } else {
+            None
+        };
+        
+        UrdfDiff {
+            robot_name,
+            links: self.diff_elements(&old.robot.links, &new.robot.links, |a, b| self.link_changes(a, b)),
+            joints: self.diff_elements(&old.robot.joints, &new.robot.joints, |a, b| self.joint_changes(a, b)),
+            materials: self.diff_elements(&old.robot.materials, &new.robot.materials, |a, b| self.material_changes(a, b)),
+        }
+    }
+
+    pub fn link_geometry_counts(&self, doc: &UrdfDocument) -> Vec<LinkGeometryCount> {
+        let mut counts: Vec<LinkGeometryCount> = doc.robot.links.values()
+            .map(|link| LinkGeometryCount {
+                link: link.name.clone(),
+                visuals: link.visual.len(),
+                collisions: link.collision.len(),
+            })
+            .collect();
+        
+        counts.sort_by(|a, b| {
+            (b.visuals + b.collisions).cmp(&(a.visuals + a.collisions))
+                .then_with(|| a.link.cmp(&b.link))
+        });
+        counts
+    }
+
+    pub fn link_masses(&self, doc: &UrdfDocument) -> Vec<LinkMass> {
+        let mut masses: Vec<LinkMass> = doc.robot.links.values()
+            .map(|link| LinkMass {
+                link: link.name.clone(),
+                mass: link.inertial.as_ref().map(|inertial| inertial.mass).unwrap_or(0.0),
+            })
+            .collect();
         
-        let link_names: Vec<&String> = doc.robot.links.keys().collect();
-        let joint_names: Vec<&String> = doc.robot.joints.keys().collect();
-        let material_names: Vec<&String> = doc.robot.materials.keys().collect();
+        masses.sort_by(|a, b| b.mass.total_cmp(&a.mass).then_with(|| a.link.cmp(&b.link)));
+        masses
+    }
+
+    pub fn center_of_mass(&self, doc: &UrdfDocument) -> Option<[f64; 3]> {
+        let roots = self.find_root_links(doc);
+        let [root] = roots.as_slice() else {
+            return None;
+        };
+        
+        let mut weighted = [0.0; 3];
+        let mut total_mass = 0.0;
+        let mut visited = HashSet::new();
+        let mut stack = vec![(root.clone(), IDENTITY)];
         
-        self.find_duplicate_names("links", &link_names, &mut duplicates);
-        self.find_duplicate_names("joints", &joint_names, &mut duplicates);
-        self.find_duplicate_names("materials", &material_names, &mut duplicates);
+        while let Some((name, frame)) = stack.pop() {
+            if !visited.insert(name.clone()) {
+                continue;
+            }
+            
+            if let Some(inertial) = doc.robot.links.get(&name).and_then(|link| link.inertial.as_ref()) {
+                let com = compose(frame, origin_pose(inertial.origin.as_ref())).pos;
+                for (sum, coordinate) in weighted.iter_mut().zip(com) {
+                    *sum += inertial.mass * coordinate;
+                }
+                total_mass += inertial.mass;
+            }
+            
+            for joint in doc.robot.joints.values().filter(|joint| joint.parent == name) {
+                stack.push((joint.child.clone(), compose(frame, origin_pose(joint.origin.as_ref()))));
+            }
+        }
+        
+        if total_mass <= 0.0 {
+            return None;
+        }
+        Some(weighted.map(|sum| sum / total_mass))
+    }
+
+    pub fn find_duplicates(&self, doc: &UrdfDocument) -> BTreeMap<String, Vec<String>> {
+        let mut duplicates: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        
+        for duplicate in &doc.robot.duplicates {
+            let names = duplicates.entry(format!("{}s", duplicate.kind)).or_default();
+            if !names.contains(&duplicate.name) {
+                names.push(duplicate.name.clone());
+            }
+        }
         
         duplicates
     }
@@ -142,23 +399,44 @@ impl UrdfProcessor {
             .collect()
     }
 
-    pub fn validate_kinematic_tree(&self, doc: &UrdfDocument) -> Result<(), Vec<String>> {
-        let mut errors = Vec::new();
+    pub fn find_chain(&self, doc: &UrdfDocument, root: &str, tip: &str) -> Option<KinematicChain> {
+        let graph = self.build_adjacency_list(&doc.robot);
+        self.find_path(&graph, root, tip, &doc.robot)
+    }
+
+    pub fn find_cycle(&self, doc: &UrdfDocument) -> Option<Vec<String>> {
+        let graph = self.build_adjacency_list(&doc.robot);
+        let mut visited = HashSet::new();
+        let mut stack = Vec::new();
         
-        let root_links = self.find_root_links(doc);
-        if root_links.len() != 1 {
-            errors.push(format!("Expected exactly 1 root link, found {}: {:?}", root_links.len(), root_links));
+        for link in doc.robot.links.keys() {
+            if !visited.contains(link)
+                && let Some(cycle) = self.dfs_find_cycle(&graph, link, &mut visited, &mut stack)
+            {
+                return Some(cycle);
+            }
         }
         
-        if self.has_cycles(doc) {
-            errors.push("Kinematic tree contains cycles".to_string());
-        }
+        None
+    }
+
+    pub fn validate_kinematic_tree(&self, doc: &UrdfDocument) -> Result<(), Vec<String>> {
+        let mut errors: Vec<String> = self.kinematic_tree_errors(doc).into_iter().map(|(_, error)| error).collect();
         
         let orphaned_links = self.find_orphaned_links(doc);
         if !orphaned_links.is_empty() {
             errors.push(format!("Found orphaned links: {:?}", orphaned_links));
         }
         
+        let components = self.find_connected_components(doc);
+        if components.len() > 1 {
+            let listed: Vec<String> = components.iter()
+                .enumerate()
+                .map(|(index, links)| format!("#{} ({} links): {}", index + 1, links.len(), links.join(", ")))
+                .collect();
+            errors.push(format!("Robot is split into {} disconnected components: {}", components.len(), listed.join("; ")));
+        }
+        
         if errors.is_empty() {
             Ok(())
         } else {
@@ -166,8 +444,53 @@ impl UrdfProcessor {
         }
     }
 
-    fn count_joint_types(&self, robot: &Robot) -> HashMap<String, usize> {
-        let mut counts = HashMap::new();
+    pub fn find_connected_components(&self, doc: &UrdfDocument) -> Vec<Vec<String>> {
+        let names: Vec<&String> = doc.robot.links.keys().collect();
+        let index: HashMap<&String, usize> = names.iter().enumerate().map(|(i, name)| (*name, i)).collect();
+        let mut parent: Vec<usize> = (0..names.len()).collect();
+        
+        for joint in doc.robot.joints.values() {
+            if let (Some(&a), Some(&b)) = (index.get(&joint.parent), index.get(&joint.child)) {
+                let (a, b) = (self.component_root(&mut parent, a), self.component_root(&mut parent, b));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+        
+        let mut components: IndexMap<usize, Vec<String>> = IndexMap::new();
+        for (i, name) in names.iter().enumerate() {
+            let root = self.component_root(&mut parent, i);
+            components.entry(root).or_default().push((*name).clone());
+        }
+        components.into_values().collect()
+    }
+
+    fn component_root(&self, parent: &mut [usize], mut node: usize) -> usize {
+        while parent[node] != node {
+            parent[node] = parent[parent[node]];
+            node = parent[node];
+        }
+        node
+    }
+
+    fn kinematic_tree_errors(&self, doc: &UrdfDocument) -> Vec<(&'static str, String)> {
+        let mut errors = Vec::new();
+        
+        let root_links = self.find_root_links(doc);
+        if root_links.is_empty() && !doc.robot.links.is_empty() {
+            errors.push(("structure/root-count", "Expected exactly 1 root link, found 0: every link is the child of a joint".to_string()));
+        } else if root_links.len() > 1 {
+            errors.push(("structure/root-count", format!("Expected exactly 1 root link, found {}: {}", root_links.len(), self.describe_roots(doc, &root_links))));
+        }
+        
+        if let Some(cycle) = self.find_cycle(doc) {
+            errors.push(("structure/cycle", format!("Kinematic tree contains a cycle: {}", cycle.join(" -> "))));
+        }
+        
+        errors
+    }
+
+    fn count_joint_types(&self, robot: &Robot) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
         
         for joint in robot.joints.values() {
             *counts.entry(joint.joint_type.clone()).or_insert(0) += 1;
@@ -210,9 +533,10 @@ impl UrdfProcessor {
             return 0;
         }
         
+        let mut depths = HashMap::new();
         let mut max_depth = 0;
         for root in &root_links {
-            let depth = self.dfs_depth(&graph, root, &mut HashSet::new());
+            let depth = self.dfs_depth(&graph, root, &mut depths, &mut HashSet::new());
             max_depth = max_depth.max(depth);
         }
         
@@ -239,11 +563,24 @@ impl UrdfProcessor {
     fn check_naming_conventions(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
         let mut issues = Vec::new();
         
+        // An empty name is a structure error (naming/empty-robot-name), shared with `validate`.
+        if !doc.robot.name.is_empty() && !self.is_valid_name(&doc.robot.name) {
+            issues.push(UrdfIssue {
+                severity: IssueSeverity::Warning,
+                category: IssueCategory::Naming,
+                rule_id: "naming/invalid-robot-name",
+                message: format!("Robot name '{}' doesn't follow naming conventions", doc.robot.name),
+                element_name: Some(doc.robot.name.clone()),
+                suggestion: Some("Use snake_case with descriptive names".to_string()),
+            });
+        }
+        
         for link_name in doc.robot.links.keys() {
             if !self.is_valid_name(link_name) {
                 issues.push(UrdfIssue {
                     severity: IssueSeverity::Warning,
                     category: IssueCategory::Naming,
+                    rule_id: "naming/invalid-link-name",
                     message: format!("Link name '{}' doesn't follow naming conventions", link_name),
                     element_name: Some(link_name.clone()),
                     suggestion: Some("Use snake_case with descriptive names".to_string()),
@@ -256,6 +593,7 @@ impl UrdfProcessor {
                 issues.push(UrdfIssue {
                     severity: IssueSeverity::Warning,
                     category: IssueCategory::Naming,
+                    rule_id: "naming/invalid-joint-name",
                     message: format!("Joint name '{}' doesn't follow naming conventions", joint_name),
                     element_name: Some(joint_name.clone()),
                     suggestion: Some("Use snake_case with descriptive names".to_string()),
@@ -266,24 +604,74 @@ impl UrdfProcessor {
         issues
     }
 
+    fn check_document_structure(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
+        urdf_structure_errors(doc)
+            .into_iter()
+            .map(|(rule_id, message)| {
+                let (category, suggestion) = match rule_id {
+                    "naming/empty-robot-name" => (IssueCategory::Naming, "Give the robot a descriptive snake_case name"),
+                    "validation/no-links" => (IssueCategory::Validation, "Add at least one <link>; a URDF robot needs a root link"),
+                    _ => (IssueCategory::Validation, "Define the missing link or fix the joint's parent/child reference"),
+                };
+                UrdfIssue {
+                    severity: IssueSeverity::Error,
+                    category,
+                    rule_id,
+                    message,
+                    element_name: None,
+                    suggestion: Some(suggestion.to_string()),
+                }
+            })
+            .collect()
+    }
+
     fn check_structural_issues(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
         let mut issues = Vec::new();
         
-        if let Err(validation_errors) = self.validate_kinematic_tree(doc) {
-            for error in validation_errors {
-                issues.push(UrdfIssue {
-                    severity: IssueSeverity::Error,
-                    category: IssueCategory::Structure,
-                    message: error,
-                    element_name: None,
-                    suggestion: Some("Fix kinematic tree structure".to_string()),
-                });
-            }
+        for (rule_id, error) in self.kinematic_tree_errors(doc) {
+            issues.push(UrdfIssue {
+                severity: IssueSeverity::Error,
+                category: IssueCategory::Structure,
+                rule_id,
+                message: error,
+                element_name: None,
+                suggestion: Some("Fix kinematic tree structure".to_string()),
+            });
+        }
+        
+        for link_name in self.find_orphaned_links(doc) {
+            issues.push(UrdfIssue {
+                severity: IssueSeverity::Error,
+                category: IssueCategory::Structure,
+                rule_id: "structure/orphaned-link",
+                message: format!("Link '{}' is not connected to any joint", link_name),
+                element_name: Some(link_name),
+                suggestion: Some("Attach the link with a joint, or remove it (`urdfix fix` does this when remove_orphaned_links is enabled)".to_string()),
+            });
         }
         
         issues
     }
 
+    fn check_duplicate_joint_pairs(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
+        let mut pairs: IndexMap<(&str, &str), Vec<&str>> = IndexMap::new();
+        for joint in doc.robot.joints.values() {
+            pairs.entry((joint.parent.as_str(), joint.child.as_str())).or_default().push(joint.name.as_str());
+        }
+        
+        pairs.into_iter()
+            .filter(|(_, joints)| joints.len() > 1)
+            .map(|((parent, child), joints)| UrdfIssue {
+                severity: IssueSeverity::Warning,
+                category: IssueCategory::Structure,
+                rule_id: "structure/duplicate-joint-pair",
+                message: format!("Joints {} all connect '{}' -> '{}'", joints.iter().map(|joint| format!("'{}'", joint)).collect::<Vec<_>>().join(", "), parent, child),
+                element_name: Some(joints[0].to_string()),
+                suggestion: Some("Keep one joint per parent/child pair; the others are likely copy-paste leftovers".to_string()),
+            })
+            .collect()
+    }
+
     fn check_physics_properties(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
         let mut issues = Vec::new();
         
@@ -292,11 +680,63 @@ impl UrdfProcessor {
                 issues.push(UrdfIssue {
                     severity: IssueSeverity::Warning,
                     category: IssueCategory::Physics,
+                    rule_id: "physics/missing-inertial",
                     message: format!("Link '{}' has geometry but no inertial properties", name),
                     element_name: Some(name.clone()),
                     suggestion: Some("Add inertial properties for physics simulation".to_string()),
                 });
             }
+            
+            if let Some(inertial) = link.inertial.as_ref().filter(|inertial| inertial.mass <= 0.0) {
+                // A root link is welded to the world and links on fixed joints are merged into their parent, so only moving links need mass.
+                let moves = doc.robot.joints.values().any(|joint| &joint.child == name && joint.joint_type != "fixed");
+                if moves {
+                    issues.push(UrdfIssue {
+                        severity: IssueSeverity::Error,
+                        category: IssueCategory::Physics,
+                        rule_id: "physics/non-positive-mass",
+                        message: format!("Link '{}' has non-positive mass {}", name, inertial.mass),
+                        element_name: Some(name.clone()),
+                        suggestion: Some("Provide a positive mass; zero-mass moving links make most simulators diverge".to_string()),
+                    });
+                }
+            }
+        }
+        
+        issues
+    }
+
+    fn check_inertia_tensors(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
+        let mut issues = Vec::new();
+        
+        for (name, link) in &doc.robot.links {
+            let Some(inertia) = link.inertial.as_ref().and_then(|inertial| inertial.inertia.as_ref()) else {
+                continue;
+            };
+            
+            let diagonal = [("ixx", inertia.ixx), ("iyy", inertia.iyy), ("izz", inertia.izz)];
+            let mut failures: Vec<String> = diagonal.iter()
+                .filter(|(_, value)| *value <= 0.0)
+                .map(|(axis, value)| format!("{} = {} is not positive", axis, value))
+                .collect();
+            
+            for i in 0..3 {
+                let (a, b, c) = (diagonal[i], diagonal[(i + 1) % 3], diagonal[(i + 2) % 3]);
+                if a.1 + b.1 < c.1 {
+                    failures.push(format!("{} + {} < {} ({} + {} < {})", a.0, b.0, c.0, a.1, b.1, c.1));
+                }
+            }
+            
+            for failure in failures {
+                issues.push(UrdfIssue {
+                    severity: IssueSeverity::Warning,
+                    category: IssueCategory::Physics,
+                    rule_id: "physics/implausible-inertia",
+                    message: format!("Link '{}' has an implausible inertia tensor: {}", name, failure),
+                    element_name: Some(name.clone()),
+                    suggestion: Some("Recompute the inertia; simulators reject tensors that are not positive definite".to_string()),
+                });
+            }
         }
         
         issues
@@ -307,10 +747,11 @@ impl UrdfProcessor {
         let duplicates = self.find_duplicates(doc);
         
         for (category, names) in duplicates {
-            if names.len() > 1 {
+            if !names.is_empty() {
                 issues.push(UrdfIssue {
                     severity: IssueSeverity::Error,
                     category: IssueCategory::Validation,
+                    rule_id: "validation/duplicate-element",
                     message: format!("Duplicate {} found: {:?}", category, names),
                     element_name: None,
                     suggestion: Some("Remove or rename duplicate elements".to_string()),
@@ -338,6 +779,7 @@ impl UrdfProcessor {
                 issues.push(UrdfIssue {
                     severity: IssueSeverity::Info,
                     category: IssueCategory::Style,
+                    rule_id: "style/unused-material",
                     message: format!("Unused material: '{}'", material_name),
                     element_name: Some(material_name.clone()),
                     suggestion: Some("Remove unused material or add reference".to_string()),
@@ -348,18 +790,67 @@ impl UrdfProcessor {
         issues
     }
 
+    fn check_joint_types(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
+        doc.robot.joints.iter()
+            .filter(|(_, joint)| !JOINT_TYPES.contains(&joint.joint_type.as_str()))
+            .map(|(name, joint)| {
+                let nearest = JOINT_TYPES.iter().min_by_key(|known| (edit_distance(&joint.joint_type, known), **known));
+                UrdfIssue {
+                    severity: IssueSeverity::Error,
+                    category: IssueCategory::Validation,
+                    rule_id: "validation/unknown-joint-type",
+                    message: format!("Joint '{}' has unknown type '{}'", name, joint.joint_type),
+                    element_name: Some(name.clone()),
+                    suggestion: Some(match nearest {
+                        Some(nearest) => format!("Did you mean '{}'? Valid types are: {}", nearest, JOINT_TYPES.join(", ")),
+                        None => format!("Valid types are: {}", JOINT_TYPES.join(", ")),
+                    }),
+                }
+            })
+            .collect()
+    }
+
     fn check_joint_limits(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
         let mut issues = Vec::new();
         
         for (name, joint) in &doc.robot.joints {
-            if joint.joint_type == "revolute" || joint.joint_type == "prismatic" {
-                if joint.limit.is_none() {
+            if (joint.joint_type == "revolute" || joint.joint_type == "prismatic") && joint.limit.is_none() {
+                issues.push(UrdfIssue {
+                    severity: IssueSeverity::Warning,
+                    category: IssueCategory::Physics,
+                    rule_id: "physics/missing-joint-limit",
+                    message: format!("Joint '{}' of type '{}' is missing limit specification", name, joint.joint_type),
+                    element_name: Some(name.clone()),
+                    suggestion: Some("Add limit element with upper, lower, effort, and velocity".to_string()),
+                });
+            }
+            
+            let Some(limit) = &joint.limit else {
+                continue;
+            };
+            
+            if let (Some(lower), Some(upper)) = (limit.lower, limit.upper)
+                && lower > upper
+            {
+                issues.push(UrdfIssue {
+                    severity: IssueSeverity::Error,
+                    category: IssueCategory::Physics,
+                    rule_id: "physics/inverted-joint-limit",
+                    message: format!("Joint '{}' has inverted limits: lower {} > upper {}", name, lower, upper),
+                    element_name: Some(name.clone()),
+                    suggestion: Some("Swap the lower and upper limits".to_string()),
+                });
+            }
+            
+            for (attribute, value) in [("effort", limit.effort), ("velocity", limit.velocity)] {
+                if let Some(value) = value.filter(|value| *value < 0.0) {
                     issues.push(UrdfIssue {
                         severity: IssueSeverity::Warning,
                         category: IssueCategory::Physics,
-                        message: format!("Joint '{}' of type '{}' is missing limit specification", name, joint.joint_type),
+                        rule_id: "physics/negative-joint-limit",
+                        message: format!("Joint '{}' has negative {} limit {}", name, attribute, value),
                         element_name: Some(name.clone()),
-                        suggestion: Some("Add limit element with upper, lower, effort, and velocity".to_string()),
+                        suggestion: Some(format!("Use a non-negative {} limit", attribute)),
                     });
                 }
             }
@@ -368,40 +859,365 @@ impl UrdfProcessor {
         issues
     }
 
-    fn find_duplicate_names(&self, category: &str, names: &[&String], duplicates: &mut HashMap<String, Vec<String>>) {
-        let mut name_counts = HashMap::new();
+    fn check_fixed_joint_extras(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
+        let mut issues = Vec::new();
         
-        for name in names {
-            *name_counts.entry((*name).clone()).or_insert(0) += 1;
+        for (name, joint) in &doc.robot.joints {
+            if joint.joint_type != "fixed" {
+                continue;
+            }
+            
+            let extras: Vec<&str> = [
+                ("axis", joint.axis.is_some()),
+                ("limit", joint.limit.is_some()),
+                ("dynamics", joint.dynamics.is_some()),
+            ]
+            .into_iter()
+            .filter(|(_, present)| *present)
+            .map(|(element, _)| element)
+            .collect();
+            
+            if !extras.is_empty() {
+                issues.push(UrdfIssue {
+                    severity: IssueSeverity::Warning,
+                    category: IssueCategory::Validation,
+                    rule_id: "validation/fixed-joint-extras",
+                    message: format!("Fixed joint '{}' specifies <{}>, which has no effect on a fixed joint", name, extras.join(">, <")),
+                    element_name: Some(name.clone()),
+                    suggestion: Some("Change the joint type if it should move, or remove the elements (strip_fixed_joint_extras in `urdfix fix`)".to_string()),
+                });
+            }
         }
         
-        let duplicate_names: Vec<String> = name_counts.iter()
-            .filter(|(_, &count)| count > 1)
-            .map(|(name, _)| name.clone())
-            .collect();
+        issues
+    }
+
+    fn check_joint_axes(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
+        let mut issues = Vec::new();
+        
+        for (name, joint) in &doc.robot.joints {
+            if !matches!(joint.joint_type.as_str(), "revolute" | "prismatic" | "continuous") {
+                continue;
+            }
+            let axis = match &joint.axis {
+                Some(axis) if !axis.defaulted => axis,
+                _ => {
+                    issues.push(UrdfIssue {
+                        severity: IssueSeverity::Warning,
+                        category: IssueCategory::Physics,
+                        rule_id: "physics/missing-axis",
+                        message: format!("Joint '{}' of type '{}' has no explicit axis; the URDF default \"1 0 0\" is used", name, joint.joint_type),
+                        element_name: Some(name.clone()),
+                        suggestion: Some("Add <axis xyz=\"...\"/> with the direction the joint moves along".to_string()),
+                    });
+                    continue;
+                }
+            };
+            
+            let norm = axis.xyz.iter().map(|value| value * value).sum::<f64>().sqrt();
+            if norm < AXIS_NORM_TOLERANCE {
+                issues.push(UrdfIssue {
+                    severity: IssueSeverity::Error,
+                    category: IssueCategory::Physics,
+                    rule_id: "physics/zero-axis",
+                    message: format!("Joint '{}' has a zero axis", name),
+                    element_name: Some(name.clone()),
+                    suggestion: Some("Set the axis to the unit vector the joint moves along, e.g. \"0 0 1\"".to_string()),
+                });
+            } else if (norm - 1.0).abs() > AXIS_NORM_TOLERANCE {
+                issues.push(UrdfIssue {
+                    severity: IssueSeverity::Warning,
+                    category: IssueCategory::Physics,
+                    rule_id: "physics/non-unit-axis",
+                    message: format!("Joint '{}' axis {:?} is not unit length (norm {})", name, axis.xyz, norm),
+                    element_name: Some(name.clone()),
+                    suggestion: Some("Normalize the axis, or set normalize_axes = true under [fix] in .urdfixrc and run `urdfix fix`".to_string()),
+                });
+            }
+        }
+        
+        issues
+    }
+
+    fn check_mimic_references(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
+        let mut issues = Vec::new();
         
-        if !duplicate_names.is_empty() {
-            duplicates.insert(category.to_string(), duplicate_names);
+        for (name, joint) in &doc.robot.joints {
+            let Some(mimic) = &joint.mimic else {
+                continue;
+            };
+            
+            if &mimic.joint == name {
+                issues.push(UrdfIssue {
+                    severity: IssueSeverity::Warning,
+                    category: IssueCategory::Validation,
+                    rule_id: "validation/self-mimic",
+                    message: format!("Joint '{}' mimics itself", name),
+                    element_name: Some(name.clone()),
+                    suggestion: Some("Point the mimic at the joint this one should follow".to_string()),
+                });
+            } else if !doc.robot.joints.contains_key(&mimic.joint) {
+                let nearest = self.nearest_names(&mimic.joint, doc.robot.joints.keys().filter(|other| *other != name), 3);
+                let suggestion = if nearest.is_empty() {
+                    "Point the mimic at an existing joint".to_string()
+                } else {
+                    format!("Did you mean: {}?", nearest.join(", "))
+                };
+                issues.push(UrdfIssue {
+                    severity: IssueSeverity::Error,
+                    category: IssueCategory::Validation,
+                    rule_id: "validation/undefined-mimic-joint",
+                    message: format!("Joint '{}' mimics non-existent joint '{}'", name, mimic.joint),
+                    element_name: Some(name.clone()),
+                    suggestion: Some(suggestion),
+                });
+            }
         }
+        
+        issues
     }
 
-    fn has_cycles(&self, doc: &UrdfDocument) -> bool {
-        let graph = self.build_adjacency_list(&doc.robot);
-        let mut visited = HashSet::new();
-        let mut rec_stack = HashSet::new();
+    fn check_collision_meshes(&self, doc: &UrdfDocument, options: &LintOptions) -> Vec<UrdfIssue> {
+        let mut issues = Vec::new();
         
-        for link in doc.robot.links.keys() {
-            if !visited.contains(link) {
-                if self.dfs_has_cycle(&graph, link, &mut visited, &mut rec_stack) {
-                    return true;
+        for (name, link) in &doc.robot.links {
+            for collision in &link.collision {
+                if let Some(GeometryShape::Mesh { filename, .. }) = collision.geometry.as_ref().map(|g| &g.shape) {
+                    issues.push(UrdfIssue {
+                        severity: options.collision_mesh_severity.clone(),
+                        category: IssueCategory::Geometry,
+                        rule_id: "geometry/collision-mesh",
+                        message: format!("Link '{}' uses mesh '{}' as collision geometry", name, filename),
+                        element_name: Some(name.clone()),
+                        suggestion: Some("Use a primitive (box, cylinder, sphere) or a simplified convex hull for collisions".to_string()),
+                    });
                 }
             }
         }
         
-        false
+        issues
+    }
+
+    fn check_mesh_files(&self, doc: &UrdfDocument, resolver: &MeshResolver) -> Vec<UrdfIssue> {
+        let mut issues = Vec::new();
+        
+        for (name, link) in &doc.robot.links {
+            let geometries = link.visual.iter().map(|visual| &visual.geometry)
+                .chain(link.collision.iter().map(|collision| &collision.geometry));
+            
+            for geometry in geometries.flatten() {
+                let GeometryShape::Mesh { filename, .. } = &geometry.shape else {
+                    continue;
+                };
+                
+                let Err(attempted) = resolver.resolve(filename) else {
+                    continue;
+                };
+                
+                if let Some((package, _)) = MeshResolver::find_substitution(filename)
+                    && !resolver.has_package(package)
+                {
+                    issues.push(UrdfIssue {
+                        severity: IssueSeverity::Error,
+                        category: IssueCategory::Geometry,
+                        rule_id: "geometry/missing-mesh",
+                        message: format!("Link '{}' references mesh '{}' but $(find {}) cannot be resolved: package '{}' is not on the package path", name, filename, package, package),
+                        element_name: Some(name.clone()),
+                        suggestion: Some(format!("Add the directory containing '{}' to ROS_PACKAGE_PATH or pass --package-path", package)),
+                    });
+                    continue;
+                }
+                
+                let message = if attempted.is_empty() {
+                    format!("Link '{}' references mesh '{}' but its package is not on the package path", name, filename)
+                } else {
+                    let tried: Vec<String> = attempted.iter().map(|path| path.display().to_string()).collect();
+                    format!("Link '{}' references missing mesh '{}' (tried {})", name, filename, tried.join(", "))
+                };
+                issues.push(UrdfIssue {
+                    severity: IssueSeverity::Warning,
+                    category: IssueCategory::Geometry,
+                    rule_id: "geometry/missing-mesh",
+                    message,
+                    element_name: Some(name.clone()),
+                    suggestion: Some("Check the filename, set ROS_PACKAGE_PATH or pass --package-path".to_string()),
+                });
+            }
+        }
+        
+        issues
+    }
+
+    fn labeled_origins<'a>(&self, doc: &'a UrdfDocument) -> Vec<(String, String, &'a Origin)> {
+        let mut origins: Vec<(String, String, &Origin)> = Vec::new();
+        
+        for (name, joint) in &doc.robot.joints {
+            if let Some(origin) = &joint.origin {
+                origins.push((format!("joint '{}'", name), name.clone(), origin));
+            }
+        }
+        
+        for (name, link) in &doc.robot.links {
+            if let Some(origin) = link.inertial.as_ref().and_then(|i| i.origin.as_ref()) {
+                origins.push((format!("inertial of link '{}'", name), name.clone(), origin));
+            }
+            for origin in link.visual.iter().filter_map(|v| v.origin.as_ref()) {
+                origins.push((format!("visual of link '{}'", name), name.clone(), origin));
+            }
+            for origin in link.collision.iter().filter_map(|c| c.origin.as_ref()) {
+                origins.push((format!("collision of link '{}'", name), name.clone(), origin));
+            }
+        }
+        
+        origins
+    }
+
+    fn check_swapped_origins(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
+        let mut issues = Vec::new();
+        
+        for (context, element_name, origin) in self.labeled_origins(doc) {
+            if self.looks_rotational(&origin.xyz) && self.looks_translational(&origin.rpy) {
+                issues.push(UrdfIssue {
+                    severity: IssueSeverity::Info,
+                    category: IssueCategory::Geometry,
+                    rule_id: "geometry/swapped-origin",
+                    message: format!("Origin of {} may have xyz and rpy swapped (xyz = {:?}, rpy = {:?})", context, origin.xyz, origin.rpy),
+                    element_name: Some(element_name),
+                    suggestion: Some("Swap the xyz and rpy attributes if the rotation was pasted into xyz".to_string()),
+                });
+            }
+        }
+        
+        issues
+    }
+
+    fn check_degree_rpy(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
+        let mut issues = Vec::new();
+        
+        for (context, element_name, origin) in self.labeled_origins(doc) {
+            if let Some(value) = origin.rpy.iter().copied().find(|v| v.abs() > TAU + 1e-6) {
+                issues.push(UrdfIssue {
+                    severity: IssueSeverity::Info,
+                    category: IssueCategory::Geometry,
+                    rule_id: "geometry/rpy-in-degrees",
+                    message: format!("Origin of {} has rpy component {} outside ±2π; rpy is in radians, so this may have been written in degrees ({} degrees = {:.4} rad)", context, value, value, value.to_radians()),
+                    element_name: Some(element_name),
+                    suggestion: Some("Convert the rpy to radians, or run `urdfix convert --rpy-to radians` if the whole file uses degrees".to_string()),
+                });
+            }
+        }
+        
+        issues
+    }
+
+    fn check_link_element_order(&self, doc: &UrdfDocument) -> Vec<UrdfIssue> {
+        let mut issues = Vec::new();
+        
+        for (name, link) in &doc.robot.links {
+            let ranks: Vec<usize> = link.child_order.iter()
+                .filter_map(|tag| LINK_ELEMENT_ORDER.iter().position(|canonical| canonical == tag))
+                .collect();
+            
+            if ranks.windows(2).any(|pair| pair[0] > pair[1]) {
+                issues.push(UrdfIssue {
+                    severity: IssueSeverity::Info,
+                    category: IssueCategory::Style,
+                    rule_id: "style/link-element-order",
+                    message: format!("Link '{}' children are ordered {} instead of {}", name, link.child_order.join(", "), LINK_ELEMENT_ORDER.join(", ")),
+                    element_name: Some(name.clone()),
+                    suggestion: Some("Run `urdfix format` to reorder link children".to_string()),
+                });
+            }
+        }
+        
+        issues
+    }
+
+    fn diff_elements<T>(&self, old: &IndexMap<String, T>, new: &IndexMap<String, T>, changes: impl Fn(&T, &T) -> Vec<String>) -> ElementDiff {
+        let mut diff = ElementDiff::default();
+        
+        for (name, old_element) in old {
+            match new.get(name) {
+                Some(new_element) => {
+                    let fields = changes(old_element, new_element);
+                    if !fields.is_empty() {
+                        diff.changed.push((name.clone(), fields));
+                    }
+                }
+                None => diff.removed.push(name.clone()),
+            }
+        }
+        
+        for name in new.keys() {
+            if !old.contains_key(name) {
+                diff.added.push(name.clone());
+            }
+        }
+        
+        diff
+    }
+
+    fn link_changes(&self, old: &Link, new: &Link) -> Vec<String> {
+        let mut fields = Vec::new();
+        
+        if old.inertial != new.inertial {
+            fields.push("inertial".to_string());
+        }
+        if old.visual != new.visual {
+            fields.push("visual".to_string());
+        }
+        if old.collision != new.collision {
+            fields.push("collision".to_string());
+        }
+        
+        fields
+    }
+
+    fn joint_changes(&self, old: &Joint, new: &Joint) -> Vec<String> {
+        let mut fields = Vec::new();
+        
+        if old.joint_type != new.joint_type {
+            fields.push(format!("type: {} -> {}", old.joint_type, new.joint_type));
+        }
+        if old.parent != new.parent {
+            fields.push(format!("parent: {} -> {}", old.parent, new.parent));
+        }
+        if old.child != new.child {
+            fields.push(format!("child: {} -> {}", old.child, new.child));
+        }
+        if old.origin != new.origin {
+            fields.push("origin".to_string());
+        }
+        if old.axis != new.axis {
+            fields.push("axis".to_string());
+        }
+        if old.limit != new.limit {
+            fields.push("limit".to_string());
+        }
+        if old.dynamics != new.dynamics {
+            fields.push("dynamics".to_string());
+        }
+        if old.mimic != new.mimic {
+            fields.push("mimic".to_string());
+        }
+        
+        fields
+    }
+
+    fn material_changes(&self, old: &Material, new: &Material) -> Vec<String> {
+        let mut fields = Vec::new();
+        
+        if old.color != new.color {
+            fields.push("color".to_string());
+        }
+        if old.texture != new.texture {
+            fields.push("texture".to_string());
+        }
+        
+        fields
     }
 
-    fn find_orphaned_links(&self, doc: &UrdfDocument) -> Vec<String> {
+    pub fn find_orphaned_links(&self, doc: &UrdfDocument) -> Vec<String> {
         let mut connected_links = HashSet::new();
         
         for joint in doc.robot.joints.values() {
@@ -409,7 +1225,10 @@ impl UrdfProcessor {
             connected_links.insert(joint.child.clone());
         }
         
+        // Without joints the first link is the robot's root, not an orphan.
+        let skip = usize::from(doc.robot.joints.is_empty());
         doc.robot.links.keys()
+            .skip(skip)
             .filter(|link_name| !connected_links.contains(*link_name))
             .cloned()
             .collect()
@@ -453,42 +1272,49 @@ impl UrdfProcessor {
             .collect()
     }
 
-    fn dfs_depth(&self, graph: &HashMap<String, Vec<String>>, node: &str, visited: &mut HashSet<String>) -> usize {
-        if visited.contains(node) {
+    fn dfs_depth(&self, graph: &HashMap<String, Vec<String>>, node: &str, depths: &mut HashMap<String, usize>, on_path: &mut HashSet<String>) -> usize {
+        if let Some(&depth) = depths.get(node) {
+            return depth;
+        }
+        if !on_path.insert(node.to_string()) {
             return 0;
         }
         
-        visited.insert(node.to_string());
         let mut max_depth = 0;
         
         if let Some(children) = graph.get(node) {
             for child in children {
-                let depth = self.dfs_depth(graph, child, visited);
+                let depth = self.dfs_depth(graph, child, depths, on_path);
                 max_depth = max_depth.max(depth);
             }
         }
         
+        on_path.remove(node);
+        depths.insert(node.to_string(), max_depth + 1);
         max_depth + 1
     }
 
-    fn dfs_has_cycle(&self, graph: &HashMap<String, Vec<String>>, node: &str, visited: &mut HashSet<String>, rec_stack: &mut HashSet<String>) -> bool {
+    fn dfs_find_cycle(&self, graph: &HashMap<String, Vec<String>>, node: &str, visited: &mut HashSet<String>, stack: &mut Vec<String>) -> Option<Vec<String>> {
         visited.insert(node.to_string());
-        rec_stack.insert(node.to_string());
+        stack.push(node.to_string());
         
         if let Some(children) = graph.get(node) {
             for child in children {
-                if !visited.contains(child) {
-                    if self.dfs_has_cycle(graph, child, visited, rec_stack) {
-                        return true;
-                    }
-                } else if rec_stack.contains(child) {
-                    return true;
+                if let Some(start) = stack.iter().position(|name| name == child) {
+                    let mut cycle = stack[start..].to_vec();
+                    cycle.push(child.clone());
+                    return Some(cycle);
+                }
+                if !visited.contains(child)
+                    && let Some(cycle) = self.dfs_find_cycle(graph, child, visited, stack)
+                {
+                    return Some(cycle);
                 }
             }
         }
         
-        rec_stack.remove(node);
-        false
+        stack.pop();
+        None
     }
 
     fn find_path(&self, graph: &HashMap<String, Vec<String>>, start: &str, end: &str, robot: &Robot) -> Option<KinematicChain> {
@@ -497,11 +1323,12 @@ impl UrdfProcessor {
         
         if self.dfs_path(graph, start, end, &mut path, &mut visited) {
             let joints = self.get_joints_in_path(&path, robot);
+            let length = path.len();
             Some(KinematicChain {
                 name: format!("{}_to_{}", start, end),
                 links: path,
                 joints,
-                length: path.len(),
+                length,
             })
         } else {
             None
@@ -518,10 +1345,8 @@ impl UrdfProcessor {
         
         if let Some(children) = graph.get(current) {
             for child in children {
-                if !visited.contains(child) {
-                    if self.dfs_path(graph, child, target, path, visited) {
-                        return true;
-                    }
+                if !visited.contains(child) && self.dfs_path(graph, child, target, path, visited) {
+                    return true;
                 }
             }
         }
@@ -548,9 +1373,288 @@ impl UrdfProcessor {
         joints
     }
 
+    fn looks_rotational(&self, values: &[f64; 3]) -> bool {
+        values.iter().any(|v| *v != 0.0)
+            && values.iter().filter(|v| **v != 0.0).all(|v| self.is_right_angle_multiple(*v))
+    }
+
+    fn looks_translational(&self, values: &[f64; 3]) -> bool {
+        values.iter().any(|v| *v != 0.0)
+            && values.iter().filter(|v| **v != 0.0).all(|v| !self.is_right_angle_multiple(*v))
+    }
+
+    fn is_right_angle_multiple(&self, value: f64) -> bool {
+        let quarter_turns = value / FRAC_PI_2;
+        quarter_turns.round() != 0.0 && (value - quarter_turns.round() * FRAC_PI_2).abs() < 1e-3
+    }
+
+    fn describe_roots(&self, doc: &UrdfDocument, root_links: &[String]) -> String {
+        let graph = self.get_dependency_graph(doc);
+        let subtrees: Vec<HashSet<&String>> = root_links.iter()
+            .map(|root| {
+                let mut reached = HashSet::from([root]);
+                let mut stack = vec![root];
+                while let Some(link) = stack.pop() {
+                    for child in graph.get(link).into_iter().flatten() {
+                        if reached.insert(child) {
+                            stack.push(child);
+                        }
+                    }
+                }
+                reached
+            })
+            .collect();
+        
+        let main = (0..root_links.len()).rev().max_by_key(|&index| subtrees[index].len()).unwrap_or(0);
+        let floating: Vec<String> = root_links.iter().enumerate()
+            .filter(|(index, _)| *index != main)
+            .map(|(_, root)| {
+                let misnamed = doc.robot.joints.values()
+                    .filter(|joint| !doc.robot.links.contains_key(&joint.child))
+                    .find(|joint| edit_distance(&joint.child, root) <= (root.chars().count() / 3).max(2));
+                let hint = match misnamed {
+                    Some(joint) => format!("joint '{}' names child '{}', which does not exist; did you mean '{}'?", joint.name, joint.child, root),
+                    None => {
+                        let nearest = self.nearest_names(root, subtrees[main].iter().copied(), 1);
+                        format!("no joint attaches it; add one from '{}'", nearest.first().unwrap_or(&root_links[main]))
+                    }
+                };
+                format!("'{}' ({})", root, hint)
+            })
+            .collect();
+        
+        format!("main root '{}'; floating roots: {}", root_links[main], floating.join(", "))
+    }
+
+    fn nearest_names<'a>(&self, target: &str, candidates: impl Iterator<Item = &'a String>, limit: usize) -> Vec<String> {
+        let mut scored: Vec<(usize, &String)> = candidates
+            .map(|candidate| (edit_distance(target, candidate), candidate))
+            .collect();
+        scored.sort();
+        scored.into_iter().take(limit).map(|(_, name)| name.clone()).collect()
+    }
+
     fn is_valid_name(&self, name: &str) -> bool {
         !name.is_empty() 
             && name.chars().all(|c| c.is_alphanumeric() || c == '_')
             && !name.starts_with(|c: char| c.is_ascii_digit())
     }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parser::{UrdfParser, validate_urdf_structure};
+
+    fn parse(xml: &str) -> UrdfDocument {
+        UrdfParser::parse_string(xml).unwrap()
+    }
+
+    fn rule_hits<'a>(issues: &'a [UrdfIssue], rule_id: &str) -> Vec<&'a UrdfIssue> {
+        issues.iter().filter(|issue| issue.rule_id == rule_id).collect()
+    }
+
+    #[test]
+    fn orphaned_links_are_reported_one_by_one() {
+        let doc = parse(r#"<robot name="r">
+  <link name="base"/>
+  <link name="stray_a"/>
+  <link name="stray_b"/>
+  <link name="arm"/>
+  <joint name="j1" type="fixed"><parent link="base"/><child link="arm"/></joint>
+</robot>"#);
+        let issues = UrdfProcessor.lint(&doc);
+        let orphans: Vec<_> = rule_hits(&issues, "structure/orphaned-link").iter()
+            .map(|issue| issue.element_name.as_deref().unwrap())
+            .collect();
+        assert_eq!(orphans, ["stray_a", "stray_b"]);
+        
+        let single = parse(r#"<robot name="r"><link name="base"/></robot>"#);
+        assert!(rule_hits(&UrdfProcessor.lint(&single), "structure/orphaned-link").is_empty());
+    }
+
+    #[test]
+    fn lenient_link_origin_is_a_warning() {
+        let (doc, _) = UrdfParser::parse_string_lenient(r#"<robot name="r"><link name="a"><origin xyz="1 0 0"/></link></robot>"#).unwrap();
+        let issues = UrdfProcessor.lint(&doc);
+        let hits = rule_hits(&issues, "validation/link-origin");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].severity, IssueSeverity::Warning);
+    }
+
+    #[test]
+    fn duplicate_link_is_one_issue() {
+        let doc = parse(r#"<robot name="r"><link name="a"/><link name="a"/></robot>"#);
+        assert_eq!(doc.warnings.len(), 1);
+        assert_eq!(rule_hits(&UrdfProcessor.lint(&doc), "validation/duplicate-element").len(), 1);
+    }
+
+    #[test]
+    fn tree_depth_counts_the_longest_path_to_a_shared_link() {
+        // "shared" has two parents; the short path through "x" is walked first.
+        let doc = parse(r#"<robot name="r">
+  <link name="root"/><link name="x"/><link name="y"/><link name="z"/><link name="shared"/><link name="tip"/>
+  <joint name="rx" type="fixed"><parent link="root"/><child link="x"/></joint>
+  <joint name="xs" type="fixed"><parent link="x"/><child link="shared"/></joint>
+  <joint name="ry" type="fixed"><parent link="root"/><child link="y"/></joint>
+  <joint name="yz" type="fixed"><parent link="y"/><child link="z"/></joint>
+  <joint name="zs" type="fixed"><parent link="z"/><child link="shared"/></joint>
+  <joint name="st" type="fixed"><parent link="shared"/><child link="tip"/></joint>
+</robot>"#);
+        assert_eq!(UrdfProcessor.analyze(&doc).tree_depth, 5);
+    }
+
+    #[test]
+    fn structure_suggestions_match_the_finding() {
+        let empty = parse(r#"<robot name="r"></robot>"#);
+        let issues = UrdfProcessor.lint(&empty);
+        let no_links = rule_hits(&issues, "validation/no-links");
+        assert!(no_links[0].suggestion.as_deref().unwrap().contains("Add at least one <link>"));
+        
+        let dangling = parse(r#"<robot name="r"><link name="a"/><joint name="j" type="fixed"><parent link="a"/><child link="b"/></joint></robot>"#);
+        let issues = UrdfProcessor.lint(&dangling);
+        let undefined = rule_hits(&issues, "validation/undefined-link");
+        assert!(undefined[0].suggestion.as_deref().unwrap().contains("missing link"));
+        
+        let unnamed = parse(r#"<robot name=""><link name="a"/></robot>"#);
+        assert_eq!(validate_urdf_structure(&unnamed), ["Robot name is empty"]);
+        let issues = UrdfProcessor.lint(&unnamed);
+        let empty_name = rule_hits(&issues, "naming/empty-robot-name");
+        assert_eq!(empty_name.len(), 1);
+        assert_eq!(empty_name[0].category, IssueCategory::Naming);
+        assert!(rule_hits(&issues, "naming/invalid-robot-name").is_empty());
+    }
+
+    fn inertial_link(name: &str, mass: f64) -> String {
+        format!(r#"<link name="{}"><inertial><mass value="{}"/><inertia ixx="1" ixy="0" ixz="0" iyy="1" iyz="0" izz="1"/></inertial></link>"#, name, mass)
+    }
+
+    #[test]
+    fn non_positive_mass_is_flagged_on_moving_links() {
+        let doc = parse(&format!(
+            r#"<robot name="r">{}{}{}{}
+  <joint name="spin" type="continuous"><parent link="base"/><child link="wheel"/><axis xyz="0 0 1"/></joint>
+  <joint name="slide" type="prismatic"><parent link="base"/><child link="carriage"/><axis xyz="1 0 0"/><limit lower="0" upper="1" effort="1" velocity="1"/></joint>
+  <joint name="weld" type="fixed"><parent link="base"/><child link="bracket"/></joint>
+</robot>"#,
+            inertial_link("base", 0.0), inertial_link("wheel", 0.0), inertial_link("carriage", -1.0), inertial_link("bracket", 0.0),
+        ));
+        let issues = UrdfProcessor.lint(&doc);
+        let flagged: Vec<_> = rule_hits(&issues, "physics/non-positive-mass").iter()
+            .map(|issue| (issue.element_name.as_deref().unwrap(), issue.severity.clone()))
+            .collect();
+        assert_eq!(flagged, [("wheel", IssueSeverity::Error), ("carriage", IssueSeverity::Error)]);
+    }
+
+    fn axis_robot(axes: &[(&str, &str)]) -> UrdfDocument {
+        let mut xml = String::from(r#"<robot name="r"><link name="base"/>"#);
+        for (index, (joint_type, xyz)) in axes.iter().enumerate() {
+            xml.push_str(&format!(
+                r#"<link name="l{index}"/><joint name="j{index}" type="{joint_type}"><parent link="base"/><child link="l{index}"/><axis xyz="{xyz}"/><limit lower="0" upper="1" effort="1" velocity="1"/></joint>"#
+            ));
+        }
+        xml.push_str("</robot>");
+        parse(&xml)
+    }
+
+    #[test]
+    fn zero_and_non_unit_axes_are_flagged() {
+        let doc = axis_robot(&[("revolute", "0 0 0"), ("prismatic", "0 0 2"), ("continuous", "0 0.6 0.8"), ("fixed", "0 0 0")]);
+        let issues = UrdfProcessor.lint(&doc);
+        
+        let zero = rule_hits(&issues, "physics/zero-axis");
+        assert_eq!(zero.len(), 1);
+        assert_eq!(zero[0].element_name.as_deref(), Some("j0"));
+        assert_eq!(zero[0].severity, IssueSeverity::Error);
+        
+        let non_unit = rule_hits(&issues, "physics/non-unit-axis");
+        assert_eq!(non_unit.len(), 1);
+        assert_eq!(non_unit[0].element_name.as_deref(), Some("j1"));
+    }
+
+    #[test]
+    fn fixed_joint_extras_are_flagged() {
+        let doc = parse(r#"<robot name="r">
+  <link name="base"/><link name="bracket"/><link name="plate"/>
+  <joint name="mount" type="fixed"><parent link="base"/><child link="bracket"/><axis xyz="0 0 1"/><limit lower="0" upper="1" effort="1" velocity="1"/></joint>
+  <joint name="bolt" type="fixed"><parent link="base"/><child link="plate"/></joint>
+</robot>"#);
+        let issues = UrdfProcessor.lint(&doc);
+        let extras = rule_hits(&issues, "validation/fixed-joint-extras");
+        assert_eq!(extras.len(), 1);
+        assert_eq!(extras[0].element_name.as_deref(), Some("mount"));
+        assert!(extras[0].message.contains("<axis>, <limit>"));
+    }
+
+    #[test]
+    fn missing_or_defaulted_axis_is_flagged() {
+        let doc = parse(r#"<robot name="r">
+  <link name="base"/><link name="a"/><link name="b"/><link name="c"/>
+  <joint name="bare" type="revolute"><parent link="base"/><child link="a"/><limit lower="0" upper="1" effort="1" velocity="1"/></joint>
+  <joint name="empty" type="continuous"><parent link="base"/><child link="b"/><axis/></joint>
+  <joint name="set" type="continuous"><parent link="base"/><child link="c"/><axis xyz="1 0 0"/></joint>
+</robot>"#);
+        assert!(doc.robot.joints["empty"].axis.as_ref().unwrap().defaulted);
+        assert!(!doc.robot.joints["set"].axis.as_ref().unwrap().defaulted);
+        
+        let issues = UrdfProcessor.lint(&doc);
+        let missing: Vec<_> = rule_hits(&issues, "physics/missing-axis")
+            .iter()
+            .filter_map(|issue| issue.element_name.as_deref())
+            .collect();
+        assert_eq!(missing, ["bare", "empty"]);
+    }
+
+    #[test]
+    fn repeated_lint_runs_report_identical_issues() {
+        let doc = parse(r#"<robot name="r">
+  <link name="base"/><link name="arm"/><link name="arm"/><link name="hand"/>
+  <link name="float_a"/><link name="float_b"/>
+  <joint name="shoulder" type="revolte"><parent link="base"/><child link="arm"/><origin xyz="1.5708 0 3.14159" rpy="0.05 0.1 0.02"/></joint>
+  <joint name="wrist" type="continuous"><parent link="arm"/><child link="hand"/><axis xyz="0 0 1"/><mimic joint="shouldr"/></joint>
+  <link name="tool"/>
+  <joint name="grip" type="fixed"><parent link="hand"/><child link="tool"/></joint>
+  <joint name="grip" type="fixed"><parent link="hand"/><child link="tool"/></joint>
+</robot>"#);
+        let options = LintOptions { detect_swapped_origins: true, ..LintOptions::default() };
+        let first = UrdfProcessor.lint_with_options(&doc, &options);
+        for rule_id in ["validation/duplicate-element", "validation/unknown-joint-type", "geometry/swapped-origin"] {
+            assert!(!rule_hits(&first, rule_id).is_empty(), "{} not reported", rule_id);
+        }
+        assert!(first.iter().any(|issue| issue.message.contains("shouldr")));
+        assert!(first.iter().any(|issue| issue.message.contains("float_a")));
+        
+        for _ in 0..10 {
+            assert_eq!(UrdfProcessor.lint_with_options(&parse(&doc.raw_xml), &options), first);
+        }
+    }
+
+    #[test]
+    fn equally_near_suggestions_are_picked_by_name() {
+        assert_eq!(UrdfProcessor.nearest_names("arm", ["bra", "arx", "ary", "axm"].iter().map(|name| name.to_string()).collect::<Vec<_>>().iter(), 2), ["arx", "ary"]);
+        
+        let doc = parse(r#"<robot name="r">
+  <link name="a"/><link name="b"/>
+  <joint name="j" type="prismed"><parent link="a"/><child link="b"/></joint>
+</robot>"#);
+        let issues = UrdfProcessor.lint(&doc);
+        let unknown = rule_hits(&issues, "validation/unknown-joint-type");
+        assert!(unknown[0].suggestion.as_deref().unwrap().starts_with("Did you mean 'fixed'?"), "{:?}", unknown[0].suggestion);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,116 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct MeshResolver {
+    pub base_dir: PathBuf,
+    pub package_paths: Vec<PathBuf>,
+}
+
+impl MeshResolver {
+    pub fn new(urdf_file: &str, package_paths: &[String]) -> Self {
+        let base_dir = Path::new(urdf_file).parent().map(Path::to_path_buf).unwrap_or_default();
+        
+        let mut paths: Vec<PathBuf> = package_paths.iter().map(PathBuf::from).collect();
+        if let Some(ros_package_path) = env::var_os("ROS_PACKAGE_PATH") {
+            paths.extend(env::split_paths(&ros_package_path).filter(|path| !path.as_os_str().is_empty()));
+        }
+        
+        Self { base_dir, package_paths: paths }
+    }
+
+    pub fn resolve(&self, uri: &str) -> Result<PathBuf, Vec<PathBuf>> {
+        let candidates = self.candidates(uri);
+        candidates.iter()
+            .find(|path| path.is_file())
+            .cloned()
+            .ok_or(candidates)
+    }
+
+    pub fn find_substitution(uri: &str) -> Option<(&str, &str)> {
+        let rest = uri.trim_start().strip_prefix("$(find ")?;
+        let (package, relative) = rest.split_once(')')?;
+        Some((package.trim(), relative.trim_start_matches('/')))
+    }
+
+    pub fn has_package(&self, package: &str) -> bool {
+        self.package_dirs(package).iter().any(|dir| dir.is_dir())
+    }
+
+    fn candidates(&self, uri: &str) -> Vec<PathBuf> {
+        let package_relative = uri.strip_prefix("package://")
+            .map(|rest| rest.split_once('/').unwrap_or((rest, "")))
+            .or_else(|| Self::find_substitution(uri));
+        if let Some((package, relative)) = package_relative {
+            return self.package_dirs(package)
+                .into_iter()
+                .map(|dir| dir.join(relative))
+                .collect();
+        }
+        
+        let path = Path::new(uri.strip_prefix("file://").unwrap_or(uri));
+        if path.is_absolute() {
+            vec![path.to_path_buf()]
+        } else {
+            vec![self.base_dir.join(path)]
+        }
+    }
+
+    fn package_dirs(&self, package: &str) -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        
+        for root in &self.package_paths {
+            if root.file_name().is_some_and(|name| name == package) {
+                dirs.push(root.clone());
+            }
+            dirs.push(root.join(package));
+            
+            // Workspaces often nest packages one level down (src/<repo>/<package>).
+            if let Ok(entries) = fs::read_dir(root) {
+                for entry in entries.flatten() {
+                    let nested = entry.path().join(package);
+                    if nested.is_dir() {
+                        dirs.push(nested);
+                    }
+                }
+            }
+        }
+        
+        dirs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_substitution_splits_package_and_path() {
+        assert_eq!(MeshResolver::find_substitution("$(find arm)/meshes/base.stl"), Some(("arm", "meshes/base.stl")));
+        assert_eq!(MeshResolver::find_substitution("  $(find   arm )//meshes/base.stl"), Some(("arm", "meshes/base.stl")));
+        assert_eq!(MeshResolver::find_substitution("$(find arm)"), Some(("arm", "")));
+        assert_eq!(MeshResolver::find_substitution("$(find arm/meshes/base.stl"), None);
+        assert_eq!(MeshResolver::find_substitution("package://arm/meshes/base.stl"), None);
+        assert_eq!(MeshResolver::find_substitution("$(arg arm)/base.stl"), None);
+    }
+
+    #[test]
+    fn find_substitution_resolves_against_package_paths() {
+        let root = env::temp_dir().join(format!("urdfix-resolver-{}", std::process::id()));
+        let meshes = root.join("arm").join("meshes");
+        fs::create_dir_all(&meshes).unwrap();
+        fs::write(meshes.join("base.stl"), b"solid").unwrap();
+        let resolver = MeshResolver { base_dir: PathBuf::new(), package_paths: vec![root.clone()] };
+        
+        assert!(resolver.has_package("arm"));
+        assert_eq!(resolver.resolve("$(find arm)/meshes/base.stl"), Ok(meshes.join("base.stl")));
+        assert_eq!(resolver.resolve("$(find  arm )/meshes/base.stl"), Ok(meshes.join("base.stl")));
+        
+        assert!(!resolver.has_package("gripper"));
+        let tried = resolver.resolve("$(find gripper)/meshes/base.stl").unwrap_err();
+        assert!(tried.contains(&root.join("gripper").join("meshes/base.stl")));
+        
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
\ No newline at end of file
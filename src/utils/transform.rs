@@ -0,0 +1,58 @@
+use crate::utils::parser::Origin;
+
+pub type Vec3 = [f64; 3];
+pub type Quat = [f64; 4];
+pub type Mat3 = [[f64; 3]; 3];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pose {
+    pub pos: Vec3,
+    pub quat: Quat,
+}
+
+pub const IDENTITY: Pose = Pose { pos: [0.0; 3], quat: [1.0, 0.0, 0.0, 0.0] };
+
+pub fn origin_pose(origin: Option<&Origin>) -> Pose {
+    match origin {
+        Some(origin) => Pose { pos: origin.xyz, quat: rpy_to_quat(origin.rpy) },
+        None => IDENTITY,
+    }
+}
+
+pub fn compose(parent: Pose, child: Pose) -> Pose {
+    let rotated = quat_to_matrix(parent.quat).map(|row| row[0] * child.pos[0] + row[1] * child.pos[1] + row[2] * child.pos[2]);
+    Pose {
+        pos: std::array::from_fn(|i| parent.pos[i] + rotated[i]),
+        quat: quat_mul(parent.quat, child.quat),
+    }
+}
+
+pub fn rpy_to_quat(rpy: Vec3) -> Quat {
+    let (sr, cr) = (rpy[0] / 2.0).sin_cos();
+    let (sp, cp) = (rpy[1] / 2.0).sin_cos();
+    let (sy, cy) = (rpy[2] / 2.0).sin_cos();
+    [
+        cr * cp * cy + sr * sp * sy,
+        sr * cp * cy - cr * sp * sy,
+        cr * sp * cy + sr * cp * sy,
+        cr * cp * sy - sr * sp * cy,
+    ]
+}
+
+pub fn quat_mul(a: Quat, b: Quat) -> Quat {
+    [
+        a[0] * b[0] - a[1] * b[1] - a[2] * b[2] - a[3] * b[3],
+        a[0] * b[1] + a[1] * b[0] + a[2] * b[3] - a[3] * b[2],
+        a[0] * b[2] - a[1] * b[3] + a[2] * b[0] + a[3] * b[1],
+        a[0] * b[3] + a[1] * b[2] - a[2] * b[1] + a[3] * b[0],
+    ]
+}
+
+pub fn quat_to_matrix(q: Quat) -> Mat3 {
+    let [w, x, y, z] = q;
+    [
+        [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y)],
+        [2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x)],
+        [2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y)],
+    ]
+}
\ No newline at end of file
@@ -0,0 +1,353 @@
+use crate::utils::parser::UrdfParser;
+use quick_xml::events::{BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use std::collections::HashMap;
+use std::io::Cursor;
+use thiserror::Error;
+
+const MAX_PROPERTY_DEPTH: usize = 32;
+
+#[derive(Error, Debug)]
+pub enum XacroError {
+    #[error("XML parsing error: {0}")]
+    Xml(#[from] quick_xml::Error),
+    #[error("{construct} is not supported; only xacro:property and ${{...}} substitution are expanded")]
+    Unsupported { construct: String, offset: usize },
+    #[error("xacro:property is missing the '{0}' attribute")]
+    MissingAttribute(&'static str),
+    #[error("Undefined property '{0}'")]
+    UndefinedProperty(String),
+    #[error("Property '{0}' refers to itself")]
+    RecursiveProperty(String),
+    #[error("Cannot evaluate \"{0}\": {1}")]
+    InvalidExpression(String, String),
+}
+
+pub fn expand_properties(xml: &str) -> Result<String, XacroError> {
+    let properties = collect_properties(xml)?;
+    let mut reader = Reader::from_str(xml);
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+    
+    loop {
+        let offset = reader.buffer_position() as usize;
+        match reader.read_event()? {
+            Event::Empty(e) if is_property(&e) => {
+                keep_line_count(&mut writer, &xml[offset..reader.buffer_position() as usize])?;
+            }
+            Event::Start(e) if is_property(&e) => {
+                reader.read_to_end(e.name())?;
+                keep_line_count(&mut writer, &xml[offset..reader.buffer_position() as usize])?;
+            }
+            Event::Start(e) | Event::Empty(e) if e.name().as_ref().starts_with(b"xacro:") => {
+                let construct = format!("<{}>", String::from_utf8_lossy(e.name().as_ref()));
+                return Err(XacroError::Unsupported { construct, offset });
+            }
+            Event::Start(e) => writer.write_event(Event::Start(substitute_attributes(&e, &properties)?))?,
+            Event::Empty(e) => writer.write_event(Event::Empty(substitute_attributes(&e, &properties)?))?,
+            Event::Text(text) => {
+                let content = text.unescape()?;
+                if content.contains("${") {
+                    let resolved = resolve(&content, &properties, 0)?;
+                    writer.write_event(Event::Text(BytesText::new(&resolved)))?;
+                } else {
+                    writer.write_event(Event::Text(text))?;
+                }
+            }
+            Event::Eof => break,
+            event => writer.write_event(event)?,
+        }
+    }
+    
+    Ok(String::from_utf8_lossy(&writer.into_inner().into_inner()).to_string())
+}
+
+// Removed properties leave their newlines behind so errors still point at the original lines.
+fn keep_line_count(writer: &mut Writer<Cursor<Vec<u8>>>, removed: &str) -> Result<(), XacroError> {
+    let newlines = "\n".repeat(removed.matches('\n').count());
+    if !newlines.is_empty() {
+        writer.write_event(Event::Text(BytesText::new(&newlines)))?;
+    }
+    Ok(())
+}
+
+fn is_property(element: &BytesStart) -> bool {
+    element.name().as_ref() == b"xacro:property"
+}
+
+fn collect_properties(xml: &str) -> Result<HashMap<String, String>, XacroError> {
+    let mut reader = Reader::from_str(xml);
+    let mut properties = HashMap::new();
+    
+    loop {
+        let offset = reader.buffer_position() as usize;
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) if is_property(&e) => {
+                let name = attribute(&e, b"name")?.ok_or(XacroError::MissingAttribute("name"))?;
+                let value = attribute(&e, b"value")?.ok_or_else(|| XacroError::Unsupported {
+                    construct: format!("<xacro:property name=\"{}\"> without a value attribute", name),
+                    offset,
+                })?;
+                properties.insert(name, value);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    
+    Ok(properties)
+}
+
+fn attribute(element: &BytesStart, key: &[u8]) -> Result<Option<String>, XacroError> {
+    for attr in element.attributes() {
+        let attr = attr.map_err(quick_xml::Error::from)?;
+        if attr.key.as_ref() == key {
+            return Ok(Some(attr.unescape_value()?.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+fn substitute_attributes(element: &BytesStart, properties: &HashMap<String, String>) -> Result<BytesStart<'static>, XacroError> {
+    let mut substituted = BytesStart::new(String::from_utf8_lossy(element.name().as_ref()).to_string());
+    for attr in element.attributes() {
+        let attr = attr.map_err(quick_xml::Error::from)?;
+        let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+        let value = resolve(&attr.unescape_value()?, properties, 0)?;
+        substituted.push_attribute((key.as_str(), value.as_str()));
+    }
+    Ok(substituted)
+}
+
+fn resolve(text: &str, properties: &HashMap<String, String>, depth: usize) -> Result<String, XacroError> {
+    let mut resolved = String::new();
+    let mut rest = text;
+    
+    while let Some(start) = rest.find("${") {
+        let end = rest[start..].find('}')
+            .ok_or_else(|| XacroError::InvalidExpression(rest[start..].to_string(), "missing closing '}'".to_string()))?;
+        resolved.push_str(&rest[..start]);
+        resolved.push_str(&evaluate(&rest[start + 2..start + end], properties, depth)?);
+        rest = &rest[start + end + 1..];
+    }
+    resolved.push_str(rest);
+    
+    Ok(resolved)
+}
+
+fn evaluate(expression: &str, properties: &HashMap<String, String>, depth: usize) -> Result<String, XacroError> {
+    let trimmed = expression.trim();
+    
+    // A bare property name keeps its text, so string properties such as link names work too.
+    if let Some(value) = properties.get(trimmed) {
+        return property_text(trimmed, value, properties, depth);
+    }
+    
+    let tokens = tokenize(trimmed).map_err(|reason| XacroError::InvalidExpression(trimmed.to_string(), reason))?;
+    let mut parser = ExpressionParser { tokens, position: 0, properties, depth, source: trimmed };
+    let value = parser.sum()?;
+    if parser.position < parser.tokens.len() {
+        return Err(parser.invalid("unexpected trailing input"));
+    }
+    Ok(value.to_string())
+}
+
+fn property_text(name: &str, value: &str, properties: &HashMap<String, String>, depth: usize) -> Result<String, XacroError> {
+    if depth >= MAX_PROPERTY_DEPTH {
+        return Err(XacroError::RecursiveProperty(name.to_string()));
+    }
+    resolve(value, properties, depth + 1)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Operator(char),
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.char_indices().peekable();
+    
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if matches!(c, '+' | '-' | '*' | '/' | '(' | ')') {
+            tokens.push(Token::Operator(c));
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            while let Some(&(index, c)) = chars.peek() {
+                let exponent_sign = matches!(c, '+' | '-') && expression[..index].ends_with(['e', 'E']);
+                if !(c.is_ascii_alphanumeric() || c == '.' || exponent_sign) {
+                    break;
+                }
+                end = index + c.len_utf8();
+                chars.next();
+            }
+            let number = UrdfParser::parse_float(&expression[start..end])
+                .ok_or_else(|| format!("invalid number '{}'", &expression[start..end]))?;
+            tokens.push(Token::Number(number));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(index, c)) = chars.peek() {
+                if !(c.is_alphanumeric() || c == '_') {
+                    break;
+                }
+                end = index + c.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token::Name(expression[start..end].to_string()));
+        } else {
+            return Err(format!("unsupported character '{}'", c));
+        }
+    }
+    
+    Ok(tokens)
+}
+
+struct ExpressionParser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    properties: &'a HashMap<String, String>,
+    depth: usize,
+    source: &'a str,
+}
+
+impl ExpressionParser<'_> {
+    fn sum(&mut self) -> Result<f64, XacroError> {
+        let mut value = self.product()?;
+        while let Some(operator @ ('+' | '-')) = self.peek_operator() {
+            self.position += 1;
+            let rhs = self.product()?;
+            value = if operator == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+
+    fn product(&mut self) -> Result<f64, XacroError> {
+        let mut value = self.factor()?;
+        while let Some(operator @ ('*' | '/')) = self.peek_operator() {
+            self.position += 1;
+            let rhs = self.factor()?;
+            value = if operator == '*' { value * rhs } else { value / rhs };
+        }
+        Ok(value)
+    }
+
+    fn factor(&mut self) -> Result<f64, XacroError> {
+        let token = self.tokens.get(self.position).cloned().ok_or_else(|| self.invalid("unexpected end of expression"))?;
+        self.position += 1;
+        match token {
+            Token::Number(value) => Ok(value),
+            Token::Operator('-') => Ok(-self.factor()?),
+            Token::Operator('+') => self.factor(),
+            Token::Operator('(') => {
+                let value = self.sum()?;
+                if self.peek_operator() != Some(')') {
+                    return Err(self.invalid("missing ')'"));
+                }
+                self.position += 1;
+                Ok(value)
+            }
+            Token::Name(name) if name == "pi" && !self.properties.contains_key("pi") => Ok(std::f64::consts::PI),
+            Token::Name(name) => {
+                let value = self.properties.get(&name).ok_or_else(|| XacroError::UndefinedProperty(name.clone()))?;
+                let text = property_text(&name, value, self.properties, self.depth)?;
+                UrdfParser::parse_float(text.trim())
+                    .ok_or_else(|| self.invalid(&format!("property '{}' is not a number ({})", name, text)))
+            }
+            Token::Operator(operator) => Err(self.invalid(&format!("unexpected '{}'", operator))),
+        }
+    }
+
+    fn peek_operator(&self) -> Option<char> {
+        match self.tokens.get(self.position) {
+            Some(Token::Operator(operator)) => Some(*operator),
+            _ => None,
+        }
+    }
+
+    fn invalid(&self, reason: &str) -> XacroError {
+        XacroError::InvalidExpression(self.source.to_string(), reason.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expression: &str, properties: &[(&str, &str)]) -> Result<String, XacroError> {
+        let properties = properties.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+        evaluate(expression, &properties, 0)
+    }
+
+    #[test]
+    fn tokenizer_splits_numbers_names_and_operators() {
+        assert_eq!(tokenize("2.5e-3*width -(1)").unwrap(), [
+            Token::Number(2.5e-3),
+            Token::Operator('*'),
+            Token::Name("width".to_string()),
+            Token::Operator('-'),
+            Token::Operator('('),
+            Token::Number(1.0),
+            Token::Operator(')'),
+        ]);
+        assert!(tokenize("1 ^ 2").unwrap_err().contains("'^'"));
+        assert!(tokenize("1.2.3").unwrap_err().contains("1.2.3"));
+    }
+
+    #[test]
+    fn operators_follow_arithmetic_precedence() {
+        assert_eq!(eval("1 + 2 * 3", &[]).unwrap(), "7");
+        assert_eq!(eval("(1 + 2) * 3", &[]).unwrap(), "9");
+        assert_eq!(eval("10 - 4 - 3", &[]).unwrap(), "3");
+        assert_eq!(eval("8 / 4 / 2", &[]).unwrap(), "1");
+        assert_eq!(eval("w/2 + 1", &[("w", "0.4")]).unwrap(), "1.2");
+    }
+
+    #[test]
+    fn unary_minus_binds_to_its_factor() {
+        assert_eq!(eval("-2 * 3", &[]).unwrap(), "-6");
+        assert_eq!(eval("2 * -3", &[]).unwrap(), "-6");
+        assert_eq!(eval("--1", &[]).unwrap(), "1");
+        assert_eq!(eval("-(1 + 1)", &[]).unwrap(), "-2");
+        assert_eq!(eval("-w", &[("w", "0.5")]).unwrap(), "-0.5");
+    }
+
+    #[test]
+    fn pi_is_built_in_unless_defined() {
+        assert_eq!(eval("pi", &[]).unwrap(), std::f64::consts::PI.to_string());
+        assert_eq!(eval("-pi/2", &[]).unwrap(), (-std::f64::consts::FRAC_PI_2).to_string());
+        assert_eq!(eval("pi * 2", &[("pi", "3")]).unwrap(), "6");
+    }
+
+    #[test]
+    fn properties_resolve_through_other_properties() {
+        let properties = [("base", "2"), ("double", "${base * 2}"), ("name", "arm_${base}")];
+        assert_eq!(eval("double + 1", &properties).unwrap(), "5");
+        assert_eq!(eval("name", &properties).unwrap(), "arm_2");
+        
+        let looping = [("a", "${b}"), ("b", "${a + 1}")];
+        assert!(matches!(eval("a", &looping), Err(XacroError::RecursiveProperty(_))));
+        assert!(matches!(eval("a * 2", &looping), Err(XacroError::RecursiveProperty(_))));
+    }
+
+    #[test]
+    fn unknown_names_and_bad_expressions_are_errors() {
+        assert!(matches!(eval("missing", &[]), Err(XacroError::UndefinedProperty(name)) if name == "missing"));
+        assert!(matches!(eval("1 + missing", &[]), Err(XacroError::UndefinedProperty(name)) if name == "missing"));
+        assert!(matches!(eval("name * 2", &[("name", "arm")]), Err(XacroError::InvalidExpression(..))));
+        assert!(matches!(eval("(1 + 2", &[]), Err(XacroError::InvalidExpression(..))));
+        assert!(matches!(eval("1 2", &[]), Err(XacroError::InvalidExpression(..))));
+    }
+
+    #[test]
+    fn expansion_removes_properties_and_keeps_lines() {
+        let xml = "<robot name=\"r\">\n  <xacro:property name=\"w\"\n    value=\"0.4\"/>\n  <link name=\"l\"><visual><geometry><box size=\"${w} ${w*2} 1\"/></geometry></visual></link>\n</robot>";
+        let expanded = expand_properties(xml).unwrap();
+        assert!(!expanded.contains("xacro:property"));
+        assert!(expanded.contains(r#"size="0.4 0.8 1""#));
+        assert_eq!(expanded.lines().count(), xml.lines().count());
+    }
+}
\ No newline at end of file